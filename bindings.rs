@@ -0,0 +1,293 @@
+//! Remappable input bindings for the SDL frontend
+//!
+//! Keys, mouse buttons and gamepad buttons are mapped to `InputAction`s by
+//! a table loaded from the settings file. Each action can have any number
+//! of bindings, and an action stays held while any of them is held.
+//!
+//! Bindings are written as SDL key names (`"Space"`, `"Return"`, `"P"`),
+//! `"Mouse <button>"` (`Left`, `Middle`, `Right`, `X1`, `X2`),
+//! `"Pad <button>"` using SDL controller mapping names (`a`, `b`, `start`,
+//! `back`, `dpup`, ...) or `"Touch"` for any finger on a touchscreen, e.g.:
+//!
+//! ```toml
+//! [bindings]
+//! swim = ["Space", "Up", "Mouse Left", "Touch", "Pad a"]
+//! pause = ["P"]
+//! ```
+
+use otter_swag::input::InputAction;
+use sdl2::controller::Button;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the settings inside the config directory
+pub const SETTINGS_FILE_NAME: &str = "settings.toml";
+
+/// Default settings location, e.g. `~/.config/otter-swag/settings.toml`
+pub fn default_settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("otter-swag").join(SETTINGS_FILE_NAME))
+}
+
+/// Contents of the settings file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Binding names per action name; actions left out keep their defaults
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl Settings {
+    /// Load settings; a missing file is not an error and yields the defaults
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// A physical input that can be bound to actions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(Keycode),
+    Mouse(MouseButton),
+    Pad(Button),
+    /// Any finger, as written in the table
+    Touch,
+    /// One particular finger; held separately, bound as `Touch`
+    Finger {
+        touch_id: i64,
+        finger_id: i64,
+    },
+}
+
+impl Binding {
+    pub fn parse(name: &str) -> Option<Self> {
+        if name == "Touch" {
+            return Some(Binding::Touch);
+        }
+        if let Some(button) = name.strip_prefix("Mouse ") {
+            let button = match button.to_ascii_lowercase().as_str() {
+                "left" => MouseButton::Left,
+                "middle" => MouseButton::Middle,
+                "right" => MouseButton::Right,
+                "x1" => MouseButton::X1,
+                "x2" => MouseButton::X2,
+                _ => return None,
+            };
+            return Some(Binding::Mouse(button));
+        }
+        if let Some(button) = name.strip_prefix("Pad ") {
+            return Button::from_string(&button.to_ascii_lowercase()).map(Binding::Pad);
+        }
+        Keycode::from_name(name).map(Binding::Key)
+    }
+
+    /// The entry this input is looked up under in the table
+    fn table_key(self) -> Self {
+        match self {
+            Binding::Finger { .. } => Binding::Touch,
+            binding => binding,
+        }
+    }
+}
+
+/// Bindings used for actions the settings file doesn't mention
+///
+/// Swim alone is enough to play without a keyboard: it starts runs, and
+/// holding it confirms each letter of the initials.
+fn default_bindings(action: InputAction) -> &'static [&'static str] {
+    match action {
+        InputAction::Swim => &["Space", "Mouse Left", "Touch", "Pad a"],
+        InputAction::Pause => &["P"],
+        InputAction::Start => &["Return", "Keypad Enter", "Pad start"],
+        InputAction::Leaderboard => &["Tab"],
+        InputAction::Quit => &["Escape", "Pad back"],
+    }
+}
+
+/// The binding table, plus which bindings are currently held
+#[derive(Debug, Default)]
+pub struct Bindings {
+    actions: HashMap<Binding, Vec<InputAction>>,
+    held: HashSet<Binding>,
+}
+
+impl Bindings {
+    /// Build the table from settings, falling back to the defaults per action
+    /// Returns the table and a message for every entry that was ignored
+    pub fn from_settings(settings: &Settings) -> (Self, Vec<String>) {
+        let mut bindings = Self::default();
+        let mut errors = Vec::new();
+
+        for name in settings.bindings.keys() {
+            if InputAction::from_name(name).is_none() {
+                errors.push(format!("Unknown action '{}'", name));
+            }
+        }
+
+        for &action in InputAction::ALL {
+            let names: Vec<&str> = match settings.bindings.get(action.name()) {
+                Some(names) => names.iter().map(String::as_str).collect(),
+                None => default_bindings(action).to_vec(),
+            };
+            for name in names {
+                match Binding::parse(name) {
+                    Some(binding) => bindings.bind(binding, action),
+                    None => {
+                        errors.push(format!("Unknown binding '{}' for {}", name, action.name()))
+                    }
+                }
+            }
+        }
+
+        (bindings, errors)
+    }
+
+    pub fn bind(&mut self, binding: Binding, action: InputAction) {
+        let actions = self.actions.entry(binding.table_key()).or_default();
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+
+    /// Actions bound to an input
+    pub fn actions(&self, binding: Binding) -> &[InputAction] {
+        self.actions
+            .get(&binding.table_key())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_bound_to(&self, binding: Binding, action: InputAction) -> bool {
+        self.actions(binding).contains(&action)
+    }
+
+    fn is_held(&self, action: InputAction) -> bool {
+        self.held.iter().any(|&b| self.is_bound_to(b, action))
+    }
+
+    /// Mark an input held; returns the actions that just became pressed
+    pub fn press(&mut self, binding: Binding) -> Vec<InputAction> {
+        if self.held.contains(&binding) {
+            return Vec::new();
+        }
+        let newly_pressed: Vec<_> = self
+            .actions(binding)
+            .iter()
+            .copied()
+            .filter(|&action| !self.is_held(action))
+            .collect();
+        if !self.actions(binding).is_empty() {
+            self.held.insert(binding);
+        }
+        newly_pressed
+    }
+
+    /// Mark an input released; returns the actions that just became released
+    pub fn release(&mut self, binding: Binding) -> Vec<InputAction> {
+        if !self.held.remove(&binding) {
+            return Vec::new();
+        }
+        self.actions(binding)
+            .iter()
+            .copied()
+            .filter(|&action| !self.is_held(action))
+            .collect()
+    }
+
+    /// Release everything, e.g. when the window loses focus
+    pub fn release_all(&mut self) -> Vec<InputAction> {
+        self.release_where(|_| true)
+    }
+
+    /// Release every held input matching `f`, e.g. a device that went away
+    pub fn release_where(&mut self, f: impl Fn(Binding) -> bool) -> Vec<InputAction> {
+        let held: Vec<_> = self.held.iter().copied().filter(|&b| f(b)).collect();
+        held.into_iter().flat_map(|b| self.release(b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finger(finger_id: i64) -> Binding {
+        Binding::Finger {
+            touch_id: 1,
+            finger_id,
+        }
+    }
+
+    fn swim_bindings() -> Bindings {
+        let mut bindings = Bindings::default();
+        bindings.bind(Binding::Key(Keycode::Space), InputAction::Swim);
+        bindings.bind(Binding::Mouse(MouseButton::Left), InputAction::Swim);
+        bindings.bind(Binding::Touch, InputAction::Swim);
+        bindings
+    }
+
+    #[test]
+    fn test_action_held_until_last_binding_released() {
+        let mut bindings = swim_bindings();
+        let space = Binding::Key(Keycode::Space);
+        let mouse = Binding::Mouse(MouseButton::Left);
+
+        assert_eq!(bindings.press(space), vec![InputAction::Swim]);
+        assert_eq!(bindings.press(mouse), vec![]);
+        assert_eq!(bindings.release(space), vec![]);
+        assert_eq!(bindings.release(mouse), vec![InputAction::Swim]);
+        assert_eq!(bindings.release(mouse), vec![]);
+    }
+
+    #[test]
+    fn test_repeated_press_is_swallowed() {
+        let mut bindings = swim_bindings();
+        let space = Binding::Key(Keycode::Space);
+
+        assert_eq!(bindings.press(space), vec![InputAction::Swim]);
+        assert_eq!(bindings.press(space), vec![]);
+        assert_eq!(bindings.release(space), vec![InputAction::Swim]);
+        assert_eq!(bindings.press(space), vec![InputAction::Swim]);
+    }
+
+    #[test]
+    fn test_unbound_input_is_not_held() {
+        let mut bindings = swim_bindings();
+        assert_eq!(bindings.press(Binding::Key(Keycode::Q)), vec![]);
+        assert_eq!(bindings.release_all(), vec![]);
+    }
+
+    #[test]
+    fn test_fingers_use_touch_entry() {
+        let mut bindings = swim_bindings();
+        assert!(bindings.is_bound_to(finger(7), InputAction::Swim));
+
+        assert_eq!(bindings.press(finger(1)), vec![InputAction::Swim]);
+        assert_eq!(bindings.press(finger(2)), vec![]);
+        assert_eq!(bindings.press(Binding::Key(Keycode::Space)), vec![]);
+
+        // Lifting every finger leaves swim held by the key
+        let released = bindings.release_where(|b| matches!(b, Binding::Finger { .. }));
+        assert_eq!(released, vec![]);
+        assert_eq!(
+            bindings.release(Binding::Key(Keycode::Space)),
+            vec![InputAction::Swim]
+        );
+
+        // Losing one of two fingers keeps swim held
+        bindings.press(finger(1));
+        bindings.press(finger(2));
+        assert_eq!(bindings.release_where(|b| b == finger(1)), vec![]);
+        assert_eq!(
+            bindings.release_where(|b| b == finger(2)),
+            vec![InputAction::Swim]
+        );
+    }
+}
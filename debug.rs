@@ -0,0 +1,135 @@
+//! Debug overlay for the SDL frontend, toggled with F3
+//!
+//! Outlines every entity's collision rect (dimmed while the otter can't
+//! touch it), labels entities with their index in `Game::entities`, and
+//! lists the game's internal state in the top-left corner.
+
+use otter_swag::render::{lerp, push_text, DrawCommand, TextureId};
+use otter_swag::*;
+use sdl2::pixels::Color;
+use std::time::Duration;
+
+const TEXT_X: i32 = 4;
+const TEXT_Y: i32 = 4;
+const LINE_HEIGHT: i32 = 18;
+
+const OTTER_COLOR: Color = Color::RGB(0, 255, 0);
+const INVINCIBLE_COLOR: Color = Color::RGB(255, 255, 255);
+const MISSILE_COLOR: Color = Color::RGB(255, 0, 0);
+const COIN_COLOR: Color = Color::RGB(255, 255, 0);
+const FISH_COLOR: Color = Color::RGB(0, 255, 255);
+const SEABED_COLOR: Color = Color::RGB(255, 128, 0);
+const POWER_UP_COLOR: Color = Color::RGB(0, 128, 255);
+const OTHER_COLOR: Color = Color::RGB(255, 0, 255);
+
+/// Label prefix and hitbox color for an entity drawn from `texture`
+fn entity_style(texture: TextureId) -> (&'static str, Color) {
+    match texture {
+        TextureId::Missile => ("M", MISSILE_COLOR),
+        TextureId::Coin => ("C", COIN_COLOR),
+        TextureId::Fish => ("F", FISH_COLOR),
+        TextureId::Seabed => ("S", SEABED_COLOR),
+        TextureId::PowerUps => ("P", POWER_UP_COLOR),
+        _ => ("E", OTHER_COLOR),
+    }
+}
+
+/// `rect` moved along with its sprite to where the sprite is drawn,
+/// `alpha` of the way from the previous tick to the current one
+fn interpolated(rect: Rect, motion: &Motion, alpha: f32) -> Rect {
+    Rect {
+        x: rect.x + lerp(motion.prev_x, motion.x, alpha) - motion.x,
+        y: rect.y + lerp(motion.prev_y, motion.y, alpha) - motion.y,
+        ..rect
+    }
+}
+
+/// Collision rects to outline at render `alpha`, with the color to draw each in
+pub fn hitboxes(game: &Game, alpha: f32) -> Vec<(Rect, Color)> {
+    if !matches!(game.state, GameState::Playing | GameState::Paused) {
+        return Vec::new();
+    }
+
+    let otter_color = if game.otter.is_invincible {
+        INVINCIBLE_COLOR
+    } else {
+        OTTER_COLOR
+    };
+    let otter_rect = interpolated(game.otter.get_collision_rect(), &game.otter.motion, alpha);
+    let mut rects = vec![(otter_rect, otter_color)];
+    // Dim what the otter can't touch right now, like exploding missiles
+    rects.extend(game.entities.iter().map(|e| {
+        let color = entity_style(e.texture()).1;
+        let color = if e.contact(&game.otter).is_some() {
+            color
+        } else {
+            dimmed(color)
+        };
+        (
+            interpolated(e.get_collision_rect(), e.motion(), alpha),
+            color,
+        )
+    }));
+    rects
+}
+
+fn dimmed(color: Color) -> Color {
+    Color::RGB(color.r / 3, color.g / 3, color.b / 3)
+}
+
+/// Entity labels at render `alpha` and the state readout, as font draw commands
+pub fn text_commands(game: &Game, alpha: f32, frame_time: Duration) -> Vec<DrawCommand> {
+    let mut commands = Vec::new();
+
+    if matches!(game.state, GameState::Playing | GameState::Paused) {
+        // Label each entity with its index, just above its hitbox
+        for (i, entity) in game.entities.iter().enumerate() {
+            let rect = interpolated(entity.get_collision_rect(), entity.motion(), alpha);
+            let label = format!("{}{}", entity_style(entity.texture()).0, i);
+            push_text(
+                &mut commands,
+                &label,
+                rect.x,
+                rect.y - FONT_GLYPH_HEIGHT as i32,
+            );
+        }
+    }
+
+    let otter = &game.otter;
+    // Each power-up with the score or frame count it runs until
+    let power_ups: Vec<String> = game
+        .power_ups
+        .iter()
+        .map(|(power_up, expiry)| {
+            let name = format!("{:?}", power_up).to_uppercase();
+            match expiry {
+                Expiry::AtScore(score) => format!("{} TO {}", name, score),
+                Expiry::AfterFrames(frames) => format!("{} {}F", name, frames),
+            }
+        })
+        .collect();
+    let power_ups = if power_ups.is_empty() {
+        "-".to_string()
+    } else {
+        power_ups.join(" ")
+    };
+    let lines = [
+        format!("{:?}", game.state),
+        format!("OTTER {:?} {}", otter.state, otter.frame),
+        format!("SPAWN RATE {:.1}", game.obstacle_spawn_rate),
+        format!("TIMER {:.1}", game.obstacle_timer),
+        format!("POWER UPS {}", power_ups),
+        format!(
+            "MISSILES {} COINS {} FISH {}",
+            game.missiles().count(),
+            game.coins().count(),
+            game.fish().count()
+        ),
+        format!("FRAME {:.2} MS", frame_time.as_secs_f64() * 1000.0),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        push_text(&mut commands, line, TEXT_X, TEXT_Y + i as i32 * LINE_HEIGHT);
+    }
+
+    commands
+}
@@ -1,44 +1,54 @@
 extern crate otter_swag;
 extern crate sdl2;
 
+mod bindings;
+mod debug;
+
+use bindings::{default_settings_path, Binding, Bindings, Settings};
+use otter_swag::config::GameConfig;
+use otter_swag::input::InputAction;
+use otter_swag::leaderboard::InitialsInput;
+use otter_swag::mask::CollisionMasks;
+use otter_swag::render::{push_text_centered, DrawCommand, TextureId};
+use otter_swag::replay::{Replay, ReplayInput, ReplayPlayer, ReplayRecorder};
+use otter_swag::save::{default_save_path, SaveData};
 use otter_swag::*;
-use sdl2::event::Event;
+use sdl2::controller::GameController;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
 use sdl2::mixer::{self, Channel, Chunk, AUDIO_S16LSB, DEFAULT_CHANNELS};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::{Canvas, Texture, TextureCreator};
+use sdl2::touch;
 use sdl2::video::{Window, WindowContext};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-const FRAME_DELAY_MS: u64 = 1000 / FRAMES_PER_SECOND as u64;
+/// The game logic ticks at the original 10 Hz; drawing runs at the display
+/// refresh rate and interpolates in between
+const TICK: Duration = Duration::from_millis(1000 / FRAMES_PER_SECOND as u64);
+/// Ticks to catch up on at most after a stall, e.g. the window being dragged
+const MAX_TICKS_PER_FRAME: u32 = 5;
+/// Shortest frame, in case vsync isn't available
+const MIN_FRAME_TIME: Duration = Duration::from_millis(4);
+
+/// Mouse id SDL gives mouse events emulated from touches (`SDL_TOUCH_MOUSEID`)
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+/// Touch id SDL gives touches emulated from the mouse (`SDL_MOUSE_TOUCHID`)
+const MOUSE_TOUCH_ID: i64 = -1;
+
+/// How long on-screen notices stay up, in ticks
+const NOTICE_FRAMES: u32 = 3 * FRAMES_PER_SECOND;
+const NOTICE_Y: i32 = 30;
 
 /// Convert our ClipRect to SDL2 Rect
 fn to_sdl_rect(clip: ClipRect) -> Rect {
     Rect::new(clip.x, clip.y, clip.w, clip.h)
 }
 
-/// Draw a score at the given position
-/// Returns the width of the rendered score
-fn draw_score(
-    canvas: &mut Canvas<Window>,
-    texture: &Texture,
-    digits: &[u8],
-    x: i32,
-    y: i32,
-) -> Result<i32, String> {
-    let mut current_x = x;
-    for &digit in digits {
-        let clip = NUMBER_CLIPS[digit as usize];
-        let dest = Rect::new(current_x, y, clip.w, clip.h);
-        canvas.copy(texture, to_sdl_rect(clip), dest)?;
-        current_x += DIGIT_SPACING;
-    }
-    Ok(current_x - x)
-}
-
 /// Load a BMP texture with magenta transparency
 fn load_texture<'a>(
     texture_creator: &'a TextureCreator<WindowContext>,
@@ -58,6 +68,186 @@ fn load_texture<'a>(
         .map_err(|e| format!("Failed to create texture: {}", e))
 }
 
+/// Execute draw commands against the loaded textures
+fn draw_commands(
+    canvas: &mut Canvas<Window>,
+    textures: &HashMap<TextureId, Texture>,
+    commands: Vec<DrawCommand>,
+) -> Result<(), String> {
+    for command in commands {
+        canvas.copy(
+            &textures[&command.texture],
+            command.src.map(to_sdl_rect),
+            to_sdl_rect(command.dest),
+        )?;
+    }
+    Ok(())
+}
+
+/// Command line options
+#[derive(Default)]
+struct Options {
+    /// Fixed seed for every run (`--seed <u64>`)
+    seed: Option<u64>,
+    /// Save each finished run's replay here (`--record <path>`)
+    record: Option<PathBuf>,
+    /// Play back a replay instead of reading input (`--replay <path>`)
+    replay: Option<PathBuf>,
+    /// Game tuning file (`--config <path>`)
+    config: Option<PathBuf>,
+    /// Input bindings file, instead of the per-user one (`--settings <path>`)
+    settings: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--seed" => {
+                let value = value()?;
+                let seed = value
+                    .parse()
+                    .map_err(|e| format!("Invalid seed {}: {}", value, e))?;
+                options.seed = Some(seed);
+            }
+            "--record" => options.record = Some(PathBuf::from(value()?)),
+            "--replay" => options.replay = Some(PathBuf::from(value()?)),
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--settings" => options.settings = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Apply an input to the game, recording it if a recording is in progress
+fn apply_input(game: &mut Game, recorder: &mut Option<ReplayRecorder>, input: ReplayInput) {
+    match recorder {
+        Some(recorder) => recorder.apply(input, game),
+        None => input.apply(game),
+    }
+}
+
+/// Release actions whose inputs went away
+fn release(game: &mut Game, recorder: &mut Option<ReplayRecorder>, released: Vec<InputAction>) {
+    for action in released {
+        apply_input(game, recorder, ReplayInput::Released(action));
+    }
+}
+
+/// Pause a run in progress and release actions whose inputs went away
+fn interrupt(game: &mut Game, recorder: &mut Option<ReplayRecorder>, released: Vec<InputAction>) {
+    if game.state == GameState::Playing {
+        apply_input(game, recorder, ReplayInput::Pressed(InputAction::Pause));
+    }
+    release(game, recorder, released);
+}
+
+/// Whether SDL still has a finger down on a touch device
+fn is_finger_down(touch_id: i64, finger_id: i64) -> bool {
+    (0..touch::num_touch_fingers(touch_id))
+        .filter_map(|i| touch::touch_finger(touch_id, i))
+        .any(|finger| finger.id == finger_id)
+}
+
+/// Load the input bindings, reporting (but otherwise ignoring) bad entries
+///
+/// An explicitly requested settings file must be readable; a broken
+/// per-user one only costs the player their custom bindings.
+fn load_bindings(path: Option<&Path>) -> Result<Bindings, String> {
+    let settings = match path {
+        Some(path) => {
+            Settings::load(path).map_err(|e| format!("Failed to load {}: {}", path.display(), e))?
+        }
+        None => match default_settings_path() {
+            Some(path) => Settings::load(&path).unwrap_or_else(|e| {
+                eprintln!("Ignoring unreadable settings {} ({})", path.display(), e);
+                Settings::default()
+            }),
+            None => Settings::default(),
+        },
+    };
+
+    let (bindings, errors) = Bindings::from_settings(&settings);
+    for error in errors {
+        eprintln!("Settings: {}", error);
+    }
+    Ok(bindings)
+}
+
+/// Load the save file, moving an unreadable one aside rather than overwriting it
+fn load_save(path: &Path) -> SaveData {
+    match SaveData::load(path) {
+        Ok(save) => save,
+        Err(e) => {
+            let backup = path.with_extension("toml.bak");
+            eprintln!(
+                "Ignoring unreadable save {} ({}), moving it to {}",
+                path.display(),
+                e,
+                backup.display()
+            );
+            if let Err(e) = std::fs::rename(path, &backup) {
+                eprintln!("Failed to move save aside: {}", e);
+            }
+            SaveData::default()
+        }
+    }
+}
+
+/// Record the game's high score and leaderboard in the save file, if there is one
+fn persist(save: &mut SaveData, path: Option<&Path>, game: &Game) {
+    if let Some(path) = path {
+        save.record_run(game);
+        if let Err(e) = save.save(path) {
+            eprintln!("Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Map a key to an initials entry input
+fn initials_input(keycode: Keycode) -> Option<InitialsInput> {
+    match keycode {
+        Keycode::Up => Some(InitialsInput::Next),
+        Keycode::Down => Some(InitialsInput::Prev),
+        Keycode::Left | Keycode::Backspace => Some(InitialsInput::Back),
+        Keycode::Right | Keycode::Return | Keycode::KpEnter => Some(InitialsInput::Confirm),
+        _ => {
+            let name = keycode.name();
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() => Some(InitialsInput::Letter(c)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Show the current run's seed in the window title
+fn show_seed(canvas: &mut Canvas<Window>, seed: u64) -> Result<(), String> {
+    canvas
+        .window_mut()
+        .set_title(&format!("Otter Swag - seed {}", seed))
+        .map_err(|e| e.to_string())
+}
+
+/// A short message drawn over the game, e.g. a controller being plugged in
+struct Notice {
+    text: String,
+    frames_left: u32,
+}
+
+impl Notice {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            frames_left: NOTICE_FRAMES,
+        }
+    }
+}
+
 /// Sound manager for playing game audio
 struct SoundManager {
     sounds: HashMap<SoundEffect, Chunk>,
@@ -68,14 +258,10 @@ impl SoundManager {
         let mut sounds = HashMap::new();
 
         // Try to load each sound, but don't fail if they're missing
-        if let Ok(chunk) = Chunk::from_file("assets/sounds/coin.wav") {
-            sounds.insert(SoundEffect::Coin, chunk);
-        }
-        if let Ok(chunk) = Chunk::from_file("assets/sounds/powerup.wav") {
-            sounds.insert(SoundEffect::Powerup, chunk);
-        }
-        if let Ok(chunk) = Chunk::from_file("assets/sounds/boom.wav") {
-            sounds.insert(SoundEffect::Boom, chunk);
+        for &effect in SoundEffect::ALL {
+            if let Ok(chunk) = Chunk::from_file(effect.asset_path()) {
+                sounds.insert(effect, chunk);
+            }
         }
 
         Ok(Self { sounds })
@@ -90,9 +276,32 @@ impl SoundManager {
 }
 
 fn main() -> Result<(), String> {
+    // A fixed seed replays the same run every time; otherwise each run gets a fresh one
+    let options = parse_args()?;
+    let fixed_seed = options.seed;
+    let replay = match &options.replay {
+        Some(path) => Some(
+            Replay::load(path).map_err(|e| format!("Failed to load {}: {}", path.display(), e))?,
+        ),
+        None => None,
+    };
+    let config = match &options.config {
+        Some(path) => GameConfig::load(path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?,
+        None => GameConfig::default(),
+    };
+    let mut bindings = load_bindings(options.settings.as_deref())?;
+    let masks = if config.precise_collision {
+        let masks = CollisionMasks::load().map_err(|e| format!("Failed to load masks: {}", e))?;
+        Some(Arc::new(masks))
+    } else {
+        None
+    };
+
     // Initialize SDL2
     let sdl_context = sdl2::init()?;
     let video_subsystem = sdl_context.video()?;
+    let controller_subsystem = sdl_context.game_controller()?;
 
     // Initialize audio
     let _audio = sdl_context.audio()?;
@@ -106,17 +315,18 @@ fn main() -> Result<(), String> {
         .build()
         .map_err(|e| e.to_string())?;
 
-    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let mut canvas = window
+        .into_canvas()
+        .present_vsync()
+        .build()
+        .map_err(|e| e.to_string())?;
     let texture_creator = canvas.texture_creator();
 
     // Load textures
-    let background_texture = load_texture(&texture_creator, "assets/background.bmp")?;
-    let menu_texture = load_texture(&texture_creator, "assets/menuScreens.bmp")?;
-    let otter_texture = load_texture(&texture_creator, "assets/otter.bmp")?;
-    let missile_texture = load_texture(&texture_creator, "assets/missiles.bmp")?;
-    let coin_texture = load_texture(&texture_creator, "assets/coins.bmp")?;
-    let fish_texture = load_texture(&texture_creator, "assets/LoveFish.bmp")?;
-    let numbers_texture = load_texture(&texture_creator, "assets/numbers.bmp")?;
+    let mut textures = HashMap::new();
+    for &id in TextureId::ALL {
+        textures.insert(id, load_texture(&texture_creator, id.asset_path())?);
+    }
 
     // Load sounds
     let sound_manager = SoundManager::new()?;
@@ -138,137 +348,314 @@ fn main() -> Result<(), String> {
     };
 
     // Initialize game
-    let mut game = Game::new();
+    let mut game = match (&replay, fixed_seed) {
+        (Some(replay), _) => replay
+            .new_game_with_config(config, masks.clone())
+            .map_err(|e| format!("Can't play back the replay: {} (check --config)", e))?,
+        (None, Some(seed)) => Game::with_config(config, seed),
+        (None, None) => Game::with_config(config, rand::random()),
+    };
+    game.set_collision_masks(masks);
+    show_seed(&mut canvas, game.seed())?;
+
+    // Load the persistent high score and leaderboard (replays don't touch the save file)
+    let save_path = default_save_path().filter(|_| replay.is_none());
+    let mut save = save_path.as_deref().map(load_save).unwrap_or_default();
+    save.apply_to(&mut game);
+
     let mut event_pump = sdl_context.event_pump()?;
-    let mut space_held = false;
+    let mut replay_player = replay.as_ref().map(ReplayPlayer::new);
+    let mut recorder: Option<ReplayRecorder> = None;
+
+    // Open controllers keyed by instance id (SDL reports ones already
+    // plugged in at startup as added too)
+    let mut controllers: HashMap<u32, GameController> = HashMap::new();
+    let mut notice: Option<Notice> = None;
+
+    // F3 debug overlay, and how long the last frame took to update and draw
+    let mut show_debug = false;
+    let mut last_frame_time = Duration::ZERO;
+
+    // Real time not yet simulated, consumed one tick at a time
+    let mut accumulator = Duration::ZERO;
+    let mut previous = Instant::now();
 
     'running: loop {
         let frame_start = Instant::now();
+        accumulator += frame_start - previous;
+        previous = frame_start;
+        if accumulator > TICK * MAX_TICKS_PER_FRAME {
+            accumulator = TICK * MAX_TICKS_PER_FRAME;
+        }
 
         // Handle events
+        let was_entering_initials = game.is_entering_initials();
         for event in event_pump.poll_iter() {
-            match event {
-                Event::Quit { .. }
-                | Event::KeyDown {
-                    keycode: Some(Keycode::Escape),
+            let (binding, pressed) = match event {
+                Event::Quit { .. } => break 'running,
+
+                // Don't let the otter drown while the player is in another window
+                Event::Window {
+                    win_event: WindowEvent::FocusLost,
                     ..
-                } => break 'running,
+                } => {
+                    let released = bindings.release_all();
+                    if replay_player.is_none() {
+                        interrupt(&mut game, &mut recorder, released);
+                    }
+                    continue;
+                }
+
+                // Without mouse capture a button let go outside the window is
+                // never reported, so stop treating the mouse as held there
+                Event::Window {
+                    win_event: WindowEvent::Leave,
+                    ..
+                } => {
+                    let released = bindings.release_where(|b| matches!(b, Binding::Mouse(_)));
+                    if replay_player.is_none() {
+                        release(&mut game, &mut recorder, released);
+                    }
+                    continue;
+                }
+
+                Event::ControllerDeviceAdded { which, .. } => {
+                    match controller_subsystem.open(which) {
+                        Ok(controller) => {
+                            println!("Controller connected: {}", controller.name());
+                            controllers.insert(controller.instance_id(), controller);
+                            notice = Some(Notice::new("CONTROLLER CONNECTED"));
+                        }
+                        Err(e) => eprintln!("Failed to open controller {}: {}", which, e),
+                    }
+                    continue;
+                }
+
+                // Release whatever the unplugged controller was holding, and
+                // give the player a chance to plug it back in
+                Event::ControllerDeviceRemoved { which, .. } => {
+                    if let Some(controller) = controllers.remove(&which) {
+                        println!("Controller disconnected: {}", controller.name());
+                        notice = Some(Notice::new("CONTROLLER DISCONNECTED"));
+                    }
+                    let released = bindings.release_where(|b| matches!(b, Binding::Pad(_)));
+                    if replay_player.is_none() {
+                        interrupt(&mut game, &mut recorder, released);
+                    }
+                    continue;
+                }
 
                 Event::KeyDown {
-                    keycode: Some(Keycode::Space),
+                    keycode: Some(Keycode::F3),
                     repeat: false,
                     ..
                 } => {
-                    space_held = true;
-                    game.handle_space_pressed();
+                    show_debug = !show_debug;
+                    continue;
                 }
 
+                // Initials entry after a run that made the leaderboard takes
+                // the keyboard, except for swim (which cycles the letter) and quit
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } if game.is_entering_initials()
+                    && replay_player.is_none()
+                    && !bindings.is_bound_to(Binding::Key(keycode), InputAction::Swim)
+                    && !bindings.is_bound_to(Binding::Key(keycode), InputAction::Quit) =>
+                {
+                    if let Some(input) = initials_input(keycode) {
+                        game.handle_initials_input(input);
+                    }
+                    continue;
+                }
+
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    repeat: false,
+                    ..
+                } => (Binding::Key(keycode), true),
                 Event::KeyUp {
-                    keycode: Some(Keycode::Space),
+                    keycode: Some(keycode),
                     ..
-                } => {
-                    space_held = false;
-                    game.handle_space_released();
+                } => (Binding::Key(keycode), false),
+
+                // Touches are tracked per finger, so skip SDL's emulated mouse
+                // clicks for them (and its emulated touches for mouse clicks)
+                Event::MouseButtonDown { which, .. } | Event::MouseButtonUp { which, .. }
+                    if which == TOUCH_MOUSE_ID =>
+                {
+                    continue
+                }
+                Event::FingerDown { touch_id, .. } | Event::FingerUp { touch_id, .. }
+                    if touch_id == MOUSE_TOUCH_ID =>
+                {
+                    continue
+                }
+                Event::MouseButtonDown { mouse_btn, .. } => (Binding::Mouse(mouse_btn), true),
+                Event::MouseButtonUp { mouse_btn, .. } => (Binding::Mouse(mouse_btn), false),
+                Event::FingerDown {
+                    touch_id,
+                    finger_id,
+                    ..
+                } => (
+                    Binding::Finger {
+                        touch_id,
+                        finger_id,
+                    },
+                    true,
+                ),
+                Event::FingerUp {
+                    touch_id,
+                    finger_id,
+                    ..
+                } => (
+                    Binding::Finger {
+                        touch_id,
+                        finger_id,
+                    },
+                    false,
+                ),
+                Event::ControllerButtonDown { button, .. } => (Binding::Pad(button), true),
+                Event::ControllerButtonUp { button, .. } => (Binding::Pad(button), false),
+                _ => continue,
+            };
+
+            let actions = if pressed {
+                bindings.press(binding)
+            } else {
+                bindings.release(binding)
+            };
+            for action in actions {
+                if action == InputAction::Quit && pressed {
+                    break 'running;
+                }
+
+                // Live input is ignored while a replay is playing
+                if replay_player.is_some() {
+                    continue;
                 }
 
-                _ => {}
+                let input = if pressed {
+                    ReplayInput::Pressed(action)
+                } else {
+                    ReplayInput::Released(action)
+                };
+                let starts_run = matches!(action, InputAction::Swim | InputAction::Start);
+                if pressed && starts_run && game.starts_run_on_press() {
+                    if fixed_seed.is_none() {
+                        game.set_seed(rand::random());
+                    }
+                    println!("Starting run with seed {}", game.seed());
+                    show_seed(&mut canvas, game.seed())?;
+                    recorder = Some(ReplayRecorder::new(game.seed(), &game.config));
+                }
+                apply_input(&mut game, &mut recorder, input);
             }
         }
 
-        // Keep swimming up while space is held
-        if space_held && game.state == GameState::Playing {
-            game.otter.swim_up();
+        // Catch lifted fingers SDL never reported, e.g. touches that slid off the window
+        let released = bindings.release_where(|b| match b {
+            Binding::Finger {
+                touch_id,
+                finger_id,
+            } => !is_finger_down(touch_id, finger_id),
+            _ => false,
+        });
+        if replay_player.is_none() {
+            release(&mut game, &mut recorder, released);
         }
 
-        // Update game
-        game.update();
-
-        // Play any pending sounds
-        for sound in game.take_pending_sounds() {
-            sound_manager.play(sound);
-        }
+        // Advance the game by as many whole ticks as have passed
+        while accumulator >= TICK {
+            accumulator -= TICK;
 
-        // Render
-        canvas.set_draw_color(Color::RGB(0, 0, 0));
-        canvas.clear();
+            // Update game, either from the replay or from live input
+            let was_playing = matches!(game.state, GameState::Playing | GameState::Paused);
+            match &mut replay_player {
+                Some(player) => {
+                    player.step(&mut game);
+                }
+                None => {
+                    game.update();
+                    if let Some(recorder) = &mut recorder {
+                        recorder.end_frame();
+                    }
+                }
+            }
 
-        // Draw background
-        canvas.copy(&background_texture, None, None)?;
+            // Save the high score and replay once the run is over
+            if was_playing && game.state == GameState::GameOver {
+                persist(&mut save, save_path.as_deref(), &game);
 
-        match game.state {
-            GameState::Menu => {
-                // Draw start menu (from menuScreens.bmp)
-                let src = Rect::new(12, 32, SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32);
-                canvas.copy(&menu_texture, src, None)?;
+                if let (Some(path), Some(recorder)) = (&options.record, recorder.take()) {
+                    match recorder.finish().save(path) {
+                        Ok(()) => println!("Replay saved to {}", path.display()),
+                        Err(e) => eprintln!("Failed to save replay to {}: {}", path.display(), e),
+                    }
+                }
             }
 
-            GameState::Playing => {
-                // Draw coins
-                for coin in &game.coins {
-                    let clip = coin.get_clip();
-                    let dest = Rect::new(coin.x, coin.y, clip.w, clip.h);
-                    canvas.copy(&coin_texture, to_sdl_rect(clip), dest)?;
+            // React to what happened this tick
+            for event in game.take_events() {
+                if let Some(sound) = event.sound() {
+                    sound_manager.play(sound);
                 }
-
-                // Draw fish
-                for fish in &game.fish {
-                    let clip = fish.get_clip();
-                    let dest = Rect::new(fish.x, fish.y, clip.w, clip.h);
-                    canvas.copy(&fish_texture, to_sdl_rect(clip), dest)?;
+                if let GameEvent::HighScoreBeaten { .. } = event {
+                    notice = Some(Notice::new("NEW HIGH SCORE!"));
                 }
+            }
 
-                // Draw missiles
-                for missile in &game.missiles {
-                    let clip = missile.get_clip();
-                    let dest = Rect::new(missile.x, missile.y, clip.w, clip.h);
-                    canvas.copy(&missile_texture, to_sdl_rect(clip), dest)?;
+            // Notices count down in ticks, like everything else
+            if let Some(current) = &mut notice {
+                current.frames_left -= 1;
+                if current.frames_left == 0 {
+                    notice = None;
                 }
-
-                // Draw otter
-                // Use source clip dimensions but always render to 32x32 area
-                // (matches original SDL1.2 behavior - no scaling)
-                let otter_clip = game.otter.get_clip();
-                let otter_dest = Rect::new(
-                    game.otter.x,
-                    game.otter.y,
-                    otter_clip.w,
-                    otter_clip.h,
-                );
-                // Don't set blend mode - just copy with color key transparency
-                canvas.copy(&otter_texture, to_sdl_rect(otter_clip), otter_dest)?;
-
-                // Draw score in top-right corner
-                let score_digits = game.get_score_digits();
-                let score_width = (score_digits.len() as i32) * DIGIT_SPACING;
-                let score_x = SCREEN_WIDTH - score_width - 10;
-                draw_score(&mut canvas, &numbers_texture, &score_digits, score_x, 5)?;
             }
+        }
 
-            GameState::GameOver => {
-                // Draw game over screen (x=505, y=32 in menuScreens.bmp)
-                let src = Rect::new(505, 32, SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32);
-                canvas.copy(&menu_texture, src, None)?;
-
-                // Draw final score centered on screen
-                let score_digits = game.get_score_digits();
-                let score_width = (score_digits.len() as i32) * DIGIT_SPACING;
-                let score_x = (SCREEN_WIDTH - score_width) / 2;
-                draw_score(&mut canvas, &numbers_texture, &score_digits, score_x, 135)?;
-
-                // Draw high score below
-                let high_score_digits = game.get_high_score_digits();
-                let high_score_width = (high_score_digits.len() as i32) * DIGIT_SPACING;
-                let high_score_x = (SCREEN_WIDTH - high_score_width) / 2;
-                draw_score(&mut canvas, &numbers_texture, &high_score_digits, high_score_x, 175)?;
+        // Save the leaderboard once initials have been entered, by a key or
+        // by holding swim through a tick
+        if was_entering_initials && !game.is_entering_initials() {
+            persist(&mut save, save_path.as_deref(), &game);
+        }
+
+        // Render
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        // Execute the game's draw list, placing entities between the last two ticks
+        let alpha = accumulator.as_secs_f32() / TICK.as_secs_f32();
+        draw_commands(
+            &mut canvas,
+            &textures,
+            game.render_commands_interpolated(alpha),
+        )?;
+
+        if show_debug {
+            for (rect, color) in debug::hitboxes(&game, alpha) {
+                canvas.set_draw_color(color);
+                canvas.draw_rect(to_sdl_rect(rect.into()))?;
             }
+            let commands = debug::text_commands(&game, alpha, last_frame_time);
+            draw_commands(&mut canvas, &textures, commands)?;
+        }
+
+        // Draw the current notice on top, if any
+        if let Some(current) = &notice {
+            let mut commands = Vec::new();
+            push_text_centered(&mut commands, &current.text, NOTICE_Y);
+            draw_commands(&mut canvas, &textures, commands)?;
         }
 
+        last_frame_time = frame_start.elapsed();
         canvas.present();
 
-        // Frame rate limiting (10 FPS like original)
+        // Vsync paces the loop; this only stops it spinning without it
         let frame_time = frame_start.elapsed();
-        if frame_time < Duration::from_millis(FRAME_DELAY_MS) {
-            std::thread::sleep(Duration::from_millis(FRAME_DELAY_MS) - frame_time);
+        if frame_time < MIN_FRAME_TIME {
+            std::thread::sleep(MIN_FRAME_TIME - frame_time);
         }
     }
 
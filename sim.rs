@@ -0,0 +1,294 @@
+//! Headless batch simulation for balancing
+//!
+//! Runs `Game::update` without SDL as fast as possible, one run per seed,
+//! driven by an input policy, and prints aggregate statistics.
+//!
+//! Usage: otter-sim [--runs N] [--seed FIRST] [--policy idle|pulse|random|bot]
+//!                  [--max-frames N] [--config FILE]
+
+extern crate otter_swag;
+
+use otter_swag::config::GameConfig;
+use otter_swag::mask::CollisionMasks;
+use otter_swag::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+
+/// Decides every frame whether the swim button should be held
+trait Policy {
+    fn wants_swim(&mut self, game: &Game) -> bool;
+}
+
+/// Never presses anything after starting
+struct Idle;
+
+impl Policy for Idle {
+    fn wants_swim(&mut self, _game: &Game) -> bool {
+        false
+    }
+}
+
+/// Holds for `period` frames, releases for `period` frames
+struct Pulse {
+    period: u32,
+}
+
+impl Policy for Pulse {
+    fn wants_swim(&mut self, game: &Game) -> bool {
+        (game.stats.frames / self.period).is_multiple_of(2)
+    }
+}
+
+/// Mashes the button at random, with its own seeded RNG
+struct RandomMasher {
+    rng: StdRng,
+    held: bool,
+}
+
+impl Policy for RandomMasher {
+    fn wants_swim(&mut self, _game: &Game) -> bool {
+        if self.rng.gen_range(0..4) == 0 {
+            self.held = !self.held;
+        }
+        self.held
+    }
+}
+
+/// Hovers mid-screen and dodges the nearest incoming missile
+struct Bot;
+
+impl Bot {
+    const TARGET_Y: i32 = 150;
+    const LOOKAHEAD_X: i32 = 140;
+    const DANGER_MARGIN: i32 = 36;
+}
+
+impl Policy for Bot {
+    fn wants_swim(&mut self, game: &Game) -> bool {
+        let otter = &game.otter;
+        let otter_rect = otter.get_collision_rect();
+        let (_, otter_center) = otter_rect.center();
+
+        // Anything that would end the run (nothing does while invincible)
+        let threat = game
+            .entities
+            .iter()
+            .filter(|e| e.contact(otter) == Some(Contact::Fatal))
+            .map(|e| e.get_collision_rect())
+            .filter(|r| r.right() >= otter_rect.left())
+            .filter(|r| r.left() - otter_rect.left() <= Self::LOOKAHEAD_X)
+            .filter(|r| (r.center().1 - otter_center).abs() < Self::DANGER_MARGIN)
+            .min_by_key(|r| r.x);
+
+        match threat {
+            // Get out of the threat's lane, away from the nearer screen edge
+            Some(threat) => threat.center().1 > otter_center || otter_center > SCREEN_HEIGHT - 60,
+            _ => otter.motion.y > Self::TARGET_Y,
+        }
+    }
+}
+
+/// Most results to reserve space for before the runs start
+const MAX_RESERVED_RUNS: u64 = 100_000;
+
+struct Options {
+    runs: u64,
+    first_seed: u64,
+    /// One past the last seed, `first_seed + runs`
+    end_seed: u64,
+    policy: String,
+    max_frames: u32,
+    config: GameConfig,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        runs: 1000,
+        first_seed: 0,
+        end_seed: 0,
+        policy: "bot".to_string(),
+        max_frames: 20_000,
+        config: GameConfig::default(),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(format!("{} requires a value", arg))?;
+        let parse_err = |e: std::num::ParseIntError| format!("Invalid {} {}: {}", arg, value, e);
+        match arg.as_str() {
+            "--runs" => options.runs = value.parse().map_err(parse_err)?,
+            "--seed" => options.first_seed = value.parse().map_err(parse_err)?,
+            "--max-frames" => options.max_frames = value.parse().map_err(parse_err)?,
+            "--policy" => options.policy = value,
+            "--config" => {
+                options.config = GameConfig::load(value.as_ref())
+                    .map_err(|e| format!("Failed to load {}: {}", value, e))?
+            }
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    options.end_seed = options
+        .first_seed
+        .checked_add(options.runs)
+        .ok_or("--seed plus --runs is past the last seed")?;
+    Ok(options)
+}
+
+fn make_policy(name: &str, seed: u64) -> Result<Box<dyn Policy>, String> {
+    match name {
+        "idle" => Ok(Box::new(Idle)),
+        "pulse" => Ok(Box::new(Pulse { period: 3 })),
+        "random" => Ok(Box::new(RandomMasher {
+            rng: StdRng::seed_from_u64(seed),
+            held: false,
+        })),
+        "bot" => Ok(Box::new(Bot)),
+        _ => Err(format!("Unknown policy: {}", name)),
+    }
+}
+
+/// Outcome of a single simulated run
+struct RunResult {
+    score: u32,
+    stats: RunStats,
+    died: bool,
+}
+
+fn simulate(
+    config: &GameConfig,
+    masks: Option<&Arc<CollisionMasks>>,
+    seed: u64,
+    policy: &mut dyn Policy,
+    max_frames: u32,
+) -> RunResult {
+    let mut game = Game::with_config(config.clone(), seed);
+    game.set_collision_masks(masks.cloned());
+    game.handle_space_pressed();
+    game.handle_space_released();
+
+    let mut held = false;
+    while game.state == GameState::Playing && game.stats.frames < max_frames {
+        let wants = policy.wants_swim(&game);
+        if wants != held {
+            if wants {
+                game.handle_space_pressed();
+            } else {
+                game.handle_space_released();
+            }
+            held = wants;
+        }
+        game.update();
+    }
+
+    RunResult {
+        score: game.score,
+        stats: game.stats,
+        died: game.state == GameState::GameOver,
+    }
+}
+
+/// Min / mean / percentiles / max of a sample
+fn summarize(name: &str, mut values: Vec<u32>) {
+    values.sort_unstable();
+    let n = values.len();
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n as f64;
+    let pct = |p: usize| values[((n - 1) * p) / 100];
+    println!(
+        "{:<20} mean {:>9.1}  min {:>7}  p10 {:>7}  p50 {:>7}  p90 {:>7}  max {:>7}",
+        name,
+        mean,
+        values[0],
+        pct(10),
+        pct(50),
+        pct(90),
+        values[n - 1]
+    );
+}
+
+/// Text histogram of scores in ten equal-width buckets
+fn histogram(scores: &[u32]) {
+    const BUCKETS: u32 = 10;
+    const BAR_WIDTH: usize = 50;
+
+    let max = scores.iter().copied().max().unwrap_or(0);
+    let bucket_size = (max / BUCKETS).max(1) + 1;
+    let mut counts = [0usize; BUCKETS as usize];
+    for &score in scores {
+        counts[((score / bucket_size) as usize).min(counts.len() - 1)] += 1;
+    }
+    let peak = counts.iter().copied().max().unwrap_or(1).max(1);
+
+    println!("\nScore distribution:");
+    for (i, &count) in counts.iter().enumerate() {
+        let lo = i as u32 * bucket_size;
+        println!(
+            "  {:>7} - {:<7} {:>6} {}",
+            lo,
+            lo + bucket_size - 1,
+            count,
+            "#".repeat(count * BAR_WIDTH / peak)
+        );
+    }
+}
+
+fn main() -> Result<(), String> {
+    let options = parse_args()?;
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+
+    let masks = if options.config.precise_collision {
+        let masks = CollisionMasks::load().map_err(|e| format!("Failed to load masks: {}", e))?;
+        Some(Arc::new(masks))
+    } else {
+        None
+    };
+
+    // Results are small, but don't reserve for an absurd --runs up front
+    let mut results = Vec::with_capacity(options.runs.min(MAX_RESERVED_RUNS) as usize);
+    for seed in options.first_seed..options.end_seed {
+        let mut policy = make_policy(&options.policy, seed)?;
+        results.push(simulate(
+            &options.config,
+            masks.as_ref(),
+            seed,
+            policy.as_mut(),
+            options.max_frames,
+        ));
+    }
+
+    let deaths = results.iter().filter(|r| r.died).count();
+    println!(
+        "{} runs, seeds {}..{}, policy '{}': {} died, {} reached the {}-frame cap",
+        results.len(),
+        options.first_seed,
+        options.end_seed,
+        options.policy,
+        deaths,
+        results.len() - deaths,
+        options.max_frames
+    );
+    let mut totals = LifetimeStats::default();
+    for result in &results {
+        totals.add_run(&result.stats);
+    }
+    println!(
+        "deaths by missile {}, rock {}, crab {}\n",
+        totals.missile_deaths, totals.rock_deaths, totals.crab_deaths
+    );
+
+    let collect = |f: fn(&RunResult) -> u32| results.iter().map(f).collect::<Vec<_>>();
+    summarize("score", collect(|r| r.score));
+    summarize("frames survived", collect(|r| r.stats.frames));
+    summarize("coins collected", collect(|r| r.stats.coins_collected));
+    summarize("coins missed", collect(|r| r.stats.coins_missed));
+    summarize("best coin streak", collect(|r| r.stats.best_streak));
+    summarize("near misses", collect(|r| r.stats.near_misses));
+    summarize("fish eaten", collect(|r| r.stats.fish_eaten));
+    summarize("walking frames", collect(|r| r.stats.walking_frames));
+    summarize("missiles dodged", collect(|r| r.stats.missiles_dodged));
+    summarize("missiles destroyed", collect(|r| r.stats.missiles_destroyed));
+
+    histogram(&collect(|r| r.score));
+    Ok(())
+}
@@ -0,0 +1,375 @@
+//! Data-driven game tuning
+//!
+//! `GameConfig` holds every gameplay tuning value. Its defaults are the
+//! original constants from the C++ source, and any subset of fields can be
+//! overridden from a TOML file, e.g.:
+//!
+//! ```toml
+//! missile_velocity_x = 24
+//! coin_spawn_chance = 80
+//! ```
+//!
+//! `otter_physics` picks how the otter moves: `"classic"` (the original
+//! fixed up/down speed) or `"smooth"` (acceleration, buoyancy and a
+//! terminal velocity, see `config/smooth.toml`).
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How the otter responds to swimming up and letting go
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtterPhysics {
+    /// Instantly switch between `otter_velocity` up and down, as in the original
+    #[default]
+    Classic,
+    /// Sink under gravity, thrust up while swimming, and never exceed
+    /// `otter_terminal_velocity`
+    Smooth,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    // Movement, in pixels per frame
+    pub otter_velocity: i32,
+    pub missile_velocity_x: i32,
+    pub coin_velocity_y: i32,
+    pub coin_velocity_x_water: i32,
+    pub fish_velocity_x: i32,
+    pub seabed_velocity_x: i32,
+    pub crab_velocity_x: i32,
+    pub power_up_velocity_x: i32,
+
+    // Otter movement model; the smooth model's tuning is in sub-pixels
+    // (1/256 of a pixel) per frame
+    pub otter_physics: OtterPhysics,
+    pub otter_sink_acceleration: i32,
+    pub otter_thrust: i32,
+    pub otter_terminal_velocity: i32,
+
+    // Scoring
+    pub score_per_frame: u32,
+    pub coin_score: u32,
+    pub missile_destroy_score: u32,
+    /// Invincibility lasts until the score increases by this much
+    pub invincibility_score_duration: u32,
+    /// Frames after a coin in which the next one continues the combo
+    pub combo_window_frames: u32,
+    /// Cap on the combo multiplier; 1 turns combos off
+    pub combo_max_multiplier: u32,
+    /// Bonus for a missile passing within `near_miss_margin` pixels of the
+    /// otter's hitbox without hitting it
+    pub near_miss_score: u32,
+    pub near_miss_margin: i32,
+
+    // How long each pickup power-up lasts, in frames
+    pub magnet_frames: u32,
+    pub shield_frames: u32,
+    pub slow_motion_frames: u32,
+
+    // Missile kinds join the mix once the score reaches their `*_from_score`,
+    // and each has its own bonus for ramming it while invincible
+    pub homing_missile_from_score: u32,
+    pub wave_missile_from_score: u32,
+    pub fast_missile_from_score: u32,
+    pub homing_missile_destroy_score: u32,
+    pub wave_missile_destroy_score: u32,
+    pub fast_missile_destroy_score: u32,
+    /// Most a homing missile moves towards the otter per frame
+    pub homing_missile_turn: i32,
+    pub fast_missile_velocity_x: i32,
+    /// Frames a fast missile's warning shows before it launches
+    pub fast_missile_warning_frames: u32,
+
+    // Spawn chances, out of 1000 per frame
+    pub coin_spawn_chance: i32,
+    pub fish_spawn_chance: i32,
+    pub rock_spawn_chance: i32,
+    pub crab_spawn_chance: i32,
+    pub power_up_spawn_chance: i32,
+
+    // Missile spawn interval in frames: starts at `obstacle_spawn_rate_start`
+    // and drops by one for every `obstacle_spawn_rate_score_step` points,
+    // down to `obstacle_spawn_rate_min`
+    pub obstacle_spawn_rate_start: f32,
+    pub obstacle_spawn_rate_min: f32,
+    pub obstacle_spawn_rate_score_step: f32,
+
+    /// Only count a collision if the sprites' opaque pixels touch, not just
+    /// their hitboxes (the frontend must supply `mask::CollisionMasks`)
+    pub precise_collision: bool,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            otter_velocity: OTTER_VELOCITY,
+            missile_velocity_x: MISSILE_VELOCITY_X,
+            coin_velocity_y: COIN_VELOCITY_Y,
+            coin_velocity_x_water: COIN_VELOCITY_X_WATER,
+            fish_velocity_x: FISH_VELOCITY_X,
+            seabed_velocity_x: SEABED_VELOCITY_X,
+            crab_velocity_x: CRAB_VELOCITY_X,
+            power_up_velocity_x: POWER_UP_VELOCITY_X,
+            otter_physics: OtterPhysics::Classic,
+            otter_sink_acceleration: OTTER_SINK_ACCELERATION,
+            otter_thrust: OTTER_THRUST,
+            otter_terminal_velocity: OTTER_TERMINAL_VELOCITY,
+            score_per_frame: SCORE_PER_FRAME,
+            coin_score: COIN_SCORE,
+            missile_destroy_score: MISSILE_DESTROY_SCORE,
+            invincibility_score_duration: INVINCIBILITY_SCORE_DURATION,
+            combo_window_frames: COMBO_WINDOW_FRAMES,
+            combo_max_multiplier: COMBO_MAX_MULTIPLIER,
+            near_miss_score: NEAR_MISS_SCORE,
+            near_miss_margin: NEAR_MISS_MARGIN,
+            magnet_frames: MAGNET_FRAMES,
+            shield_frames: SHIELD_FRAMES,
+            slow_motion_frames: SLOW_MOTION_FRAMES,
+            homing_missile_from_score: HOMING_MISSILE_FROM_SCORE,
+            wave_missile_from_score: WAVE_MISSILE_FROM_SCORE,
+            fast_missile_from_score: FAST_MISSILE_FROM_SCORE,
+            homing_missile_destroy_score: HOMING_MISSILE_DESTROY_SCORE,
+            wave_missile_destroy_score: WAVE_MISSILE_DESTROY_SCORE,
+            fast_missile_destroy_score: FAST_MISSILE_DESTROY_SCORE,
+            homing_missile_turn: HOMING_MISSILE_TURN,
+            fast_missile_velocity_x: FAST_MISSILE_VELOCITY_X,
+            fast_missile_warning_frames: FAST_MISSILE_WARNING_FRAMES,
+            coin_spawn_chance: COIN_SPAWN_CHANCE,
+            fish_spawn_chance: FISH_SPAWN_CHANCE,
+            rock_spawn_chance: ROCK_SPAWN_CHANCE,
+            crab_spawn_chance: CRAB_SPAWN_CHANCE,
+            power_up_spawn_chance: POWER_UP_SPAWN_CHANCE,
+            obstacle_spawn_rate_start: OBSTACLE_SPAWN_RATE_START,
+            obstacle_spawn_rate_min: OBSTACLE_SPAWN_RATE_MIN,
+            obstacle_spawn_rate_score_step: OBSTACLE_SPAWN_RATE_SCORE_STEP,
+            precise_collision: false,
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_toml(text: &str) -> io::Result<Self> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reject values that parse but break the game, like hazards that move
+    /// right and so never leave the screen
+    pub fn validate(&self) -> io::Result<()> {
+        let speeds = [
+            ("otter_velocity", self.otter_velocity),
+            ("missile_velocity_x", self.missile_velocity_x),
+            ("coin_velocity_y", self.coin_velocity_y),
+            ("fish_velocity_x", self.fish_velocity_x),
+            ("seabed_velocity_x", self.seabed_velocity_x),
+            ("crab_velocity_x", self.crab_velocity_x),
+            ("power_up_velocity_x", self.power_up_velocity_x),
+            ("fast_missile_velocity_x", self.fast_missile_velocity_x),
+        ];
+        for (name, value) in speeds {
+            if value <= 0 {
+                return Err(invalid_data(format!("{} must be positive, got {}", name, value)));
+            }
+        }
+
+        // Smooth physics clamps the otter's speed to +/- the terminal velocity
+        if self.otter_terminal_velocity <= 0 {
+            return Err(invalid_data(format!(
+                "otter_terminal_velocity must be positive, got {}",
+                self.otter_terminal_velocity
+            )));
+        }
+        let accelerations = [
+            ("otter_sink_acceleration", self.otter_sink_acceleration),
+            ("otter_thrust", self.otter_thrust),
+            ("homing_missile_turn", self.homing_missile_turn),
+        ];
+        for (name, value) in accelerations {
+            if value < 0 {
+                return Err(invalid_data(format!("{} can't be negative, got {}", name, value)));
+            }
+        }
+
+        let chances = [
+            ("coin_spawn_chance", self.coin_spawn_chance),
+            ("fish_spawn_chance", self.fish_spawn_chance),
+            ("rock_spawn_chance", self.rock_spawn_chance),
+            ("crab_spawn_chance", self.crab_spawn_chance),
+            ("power_up_spawn_chance", self.power_up_spawn_chance),
+        ];
+        for (name, value) in chances {
+            if !(0..=1000).contains(&value) {
+                return Err(invalid_data(format!(
+                    "{} must be between 0 and 1000, got {}",
+                    name, value
+                )));
+            }
+        }
+
+        let rates = [
+            ("obstacle_spawn_rate_min", self.obstacle_spawn_rate_min),
+            ("obstacle_spawn_rate_score_step", self.obstacle_spawn_rate_score_step),
+        ];
+        for (name, value) in rates {
+            if value.is_nan() || value <= 0.0 {
+                return Err(invalid_data(format!("{} must be positive, got {}", name, value)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("config is always representable as TOML")
+    }
+
+    /// Hash of every setting, stored in replays so a run is never played back
+    /// with different tuning than it was recorded with
+    pub fn fingerprint(&self) -> u64 {
+        // 64-bit FNV-1a, which unlike `DefaultHasher` is stable across builds
+        self.to_toml().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
+    /// Missile spawn interval (in frames) at a given score
+    pub fn obstacle_spawn_rate(&self, score: u32) -> f32 {
+        (self.obstacle_spawn_rate_start - score as f32 / self.obstacle_spawn_rate_score_step)
+            .max(self.obstacle_spawn_rate_min)
+    }
+
+    /// Missile kinds that can spawn at a given score
+    pub fn missile_kinds(&self, score: u32) -> Vec<MissileKind> {
+        let mut kinds = vec![MissileKind::Straight];
+        if score >= self.homing_missile_from_score {
+            kinds.push(MissileKind::Homing);
+        }
+        if score >= self.wave_missile_from_score {
+            kinds.push(MissileKind::Wave);
+        }
+        if score >= self.fast_missile_from_score {
+            kinds.push(MissileKind::Fast);
+        }
+        kinds
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_file_keeps_defaults() {
+        let config = GameConfig::from_toml("coin_score = 250\nmissile_velocity_x = 30\n").unwrap();
+        assert_eq!(config.coin_score, 250);
+        assert_eq!(config.missile_velocity_x, 30);
+        assert_eq!(config.otter_velocity, OTTER_VELOCITY);
+    }
+
+    #[test]
+    fn test_unknown_field_is_error() {
+        assert!(GameConfig::from_toml("coin_scroe = 250\n").is_err());
+    }
+
+    #[test]
+    fn test_backwards_velocity_is_error() {
+        for field in ["missile_velocity_x", "fish_velocity_x", "seabed_velocity_x"] {
+            let err = GameConfig::from_toml(&format!("{} = -4\n", field)).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err.to_string().contains(field));
+        }
+        assert!(GameConfig::from_toml("crab_velocity_x = 0\n").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_spawn_settings_are_errors() {
+        assert!(GameConfig::from_toml("coin_spawn_chance = 1001\n").is_err());
+        assert!(GameConfig::from_toml("fish_spawn_chance = -1\n").is_err());
+        assert!(GameConfig::from_toml("rock_spawn_chance = 0\n").is_ok());
+        assert!(GameConfig::from_toml("obstacle_spawn_rate_score_step = 0.0\n").is_err());
+        assert!(GameConfig::from_toml("obstacle_spawn_rate_min = -1.0\n").is_err());
+        assert!(GameConfig::from_toml("homing_missile_turn = -1\n").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config = GameConfig {
+            fish_spawn_chance: 9,
+            ..GameConfig::default()
+        };
+        assert_eq!(GameConfig::from_toml(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_settings() {
+        let config = GameConfig::default();
+        assert_eq!(config.fingerprint(), GameConfig::default().fingerprint());
+        let precise = GameConfig {
+            precise_collision: true,
+            ..GameConfig::default()
+        };
+        assert_ne!(precise.fingerprint(), config.fingerprint());
+    }
+
+    #[test]
+    fn test_classic_file_matches_defaults() {
+        let text = include_str!("../config/classic.toml");
+        assert_eq!(GameConfig::from_toml(text).unwrap(), GameConfig::default());
+    }
+
+    #[test]
+    fn test_smooth_file_picks_smooth_physics() {
+        let text = include_str!("../config/smooth.toml");
+        let config = GameConfig::from_toml(text).unwrap();
+        assert_eq!(config.otter_physics, OtterPhysics::Smooth);
+        assert!(GameConfig::from_toml("otter_physics = \"floaty\"\n").is_err());
+    }
+
+    #[test]
+    fn test_bad_smooth_physics_is_error() {
+        let smooth = "otter_physics = \"smooth\"\n";
+        for line in [
+            "otter_terminal_velocity = -5",
+            "otter_terminal_velocity = 0",
+            "otter_thrust = -1",
+            "otter_sink_acceleration = -256",
+        ] {
+            let err = GameConfig::from_toml(&format!("{}{}\n", smooth, line)).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", line);
+        }
+        assert!(GameConfig::from_toml(&format!("{}otter_thrust = 0\n", smooth)).is_ok());
+    }
+
+    #[test]
+    fn test_missile_kinds_unlock_with_score() {
+        let config = GameConfig::default();
+        assert_eq!(config.missile_kinds(0), vec![MissileKind::Straight]);
+        assert_eq!(config.missile_kinds(WAVE_MISSILE_FROM_SCORE).len(), 3);
+        assert_eq!(
+            config.missile_kinds(u32::MAX).last(),
+            Some(&MissileKind::Fast)
+        );
+    }
+
+    #[test]
+    fn test_spawn_rate_curve() {
+        let config = GameConfig::default();
+        assert_eq!(config.obstacle_spawn_rate(0), 50.0);
+        assert_eq!(config.obstacle_spawn_rate(1000), 40.0);
+        assert_eq!(config.obstacle_spawn_rate(1_000_000), 10.0);
+    }
+}
@@ -0,0 +1,317 @@
+//! Playfield entities
+//!
+//! Everything that scrolls across the playfield and can touch the otter
+//! implements `Entity`. The game keeps them all in one `Vec<AnyEntity>` and
+//! updates, collides and draws them through the trait, so adding a hazard
+//! or pickup takes a type (holding its position in a `Motion`), an
+//! `AnyEntity` variant and a spawn rule; the update loop, collision checks
+//! and renderer stay as they are.
+
+use crate::mask::Sprite;
+use crate::render::TextureId;
+use crate::*;
+
+/// What an entity touching the otter does to the run
+///
+/// Contacts made in the same frame are resolved in this order, so a fatal
+/// hit ends the run before any pickups touched alongside it count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Contact {
+    /// Ends the run
+    Fatal,
+    /// Blown up by the invincible otter for a bonus of `score`
+    Destroyed { score: u32 },
+    /// Picked up for points
+    Coin,
+    /// Eaten or picked up, starting a power-up
+    PowerUp(PowerUp),
+}
+
+/// Where something is, and where it was before its last `update`
+///
+/// Rendering draws it between the two, so it glides between 10 Hz ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Motion {
+    pub x: i32,
+    pub y: i32,
+    pub prev_x: i32,
+    pub prev_y: i32,
+}
+
+impl Motion {
+    /// Standing still at (x, y)
+    pub const fn at(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y,
+            prev_x: x,
+            prev_y: y,
+        }
+    }
+
+    /// Make the previous position the current one, so interpolation stands still
+    pub fn hold(&mut self) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+    }
+}
+
+pub trait Entity {
+    /// Advance one frame
+    fn update(&mut self);
+
+    fn get_clip(&self) -> ClipRect;
+
+    fn get_collision_rect(&self) -> Rect;
+
+    /// Sprite sheet `get_clip` refers to
+    fn texture(&self) -> TextureId;
+
+    fn motion(&self) -> &Motion;
+
+    fn motion_mut(&mut self) -> &mut Motion;
+
+    /// False once the entity should be removed from the game
+    fn is_active(&self) -> bool;
+
+    /// Draw order among entities: lower depths are drawn first, further back
+    fn depth(&self) -> i32;
+
+    /// What touching `otter` would do right now, or None if it can't be touched
+    fn contact(&self, otter: &Otter) -> Option<Contact>;
+
+    /// React to having touched the otter, e.g. explode or get picked up
+    fn on_contact(&mut self);
+
+    /// See where the otter is before each `update`, for entities that chase it
+    fn aim(&mut self, _otter: &Otter) {}
+
+    /// Switch slow motion on or off before each `update`, for hazards it slows
+    fn set_slowed(&mut self, _slowed: bool) {}
+
+    /// Top-left corner of the sprite
+    fn position(&self) -> (i32, i32) {
+        (self.motion().x, self.motion().y)
+    }
+
+    /// Position before the last `update`, for interpolated rendering
+    fn prev_position(&self) -> (i32, i32) {
+        (self.motion().prev_x, self.motion().prev_y)
+    }
+
+    /// Make the previous position the current one, so interpolation stands still
+    fn hold_position(&mut self) {
+        self.motion_mut().hold();
+    }
+
+    /// The sprite as drawn this frame
+    fn sprite(&self) -> Sprite {
+        let (x, y) = self.position();
+        Sprite::new(self.texture(), self.get_clip(), x, y)
+    }
+}
+
+/// Any entity the game can hold, dispatching `Entity` to the wrapped type
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnyEntity {
+    Missile(Missile),
+    Coin(Coin),
+    Fish(Fish),
+    Rock(Rock),
+    Crab(Crab),
+    PowerUp(PowerUpPickup),
+}
+
+/// Run `$body` with `$e` bound to whichever entity `$entity` wraps
+macro_rules! dispatch {
+    ($entity:expr, $e:ident => $body:expr) => {
+        match $entity {
+            AnyEntity::Missile($e) => $body,
+            AnyEntity::Coin($e) => $body,
+            AnyEntity::Fish($e) => $body,
+            AnyEntity::Rock($e) => $body,
+            AnyEntity::Crab($e) => $body,
+            AnyEntity::PowerUp($e) => $body,
+        }
+    };
+}
+
+impl AnyEntity {
+    pub fn as_missile(&self) -> Option<&Missile> {
+        match self {
+            AnyEntity::Missile(missile) => Some(missile),
+            _ => None,
+        }
+    }
+
+    pub fn as_missile_mut(&mut self) -> Option<&mut Missile> {
+        match self {
+            AnyEntity::Missile(missile) => Some(missile),
+            _ => None,
+        }
+    }
+
+    pub fn as_coin(&self) -> Option<&Coin> {
+        match self {
+            AnyEntity::Coin(coin) => Some(coin),
+            _ => None,
+        }
+    }
+
+    pub fn as_coin_mut(&mut self) -> Option<&mut Coin> {
+        match self {
+            AnyEntity::Coin(coin) => Some(coin),
+            _ => None,
+        }
+    }
+
+    pub fn as_fish(&self) -> Option<&Fish> {
+        match self {
+            AnyEntity::Fish(fish) => Some(fish),
+            _ => None,
+        }
+    }
+}
+
+impl Entity for AnyEntity {
+    fn update(&mut self) {
+        dispatch!(self, e => e.update())
+    }
+
+    fn get_clip(&self) -> ClipRect {
+        dispatch!(self, e => e.get_clip())
+    }
+
+    fn get_collision_rect(&self) -> Rect {
+        dispatch!(self, e => e.get_collision_rect())
+    }
+
+    fn texture(&self) -> TextureId {
+        dispatch!(self, e => e.texture())
+    }
+
+    fn motion(&self) -> &Motion {
+        dispatch!(self, e => e.motion())
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        dispatch!(self, e => e.motion_mut())
+    }
+
+    fn is_active(&self) -> bool {
+        dispatch!(self, e => e.is_active())
+    }
+
+    fn depth(&self) -> i32 {
+        dispatch!(self, e => e.depth())
+    }
+
+    fn contact(&self, otter: &Otter) -> Option<Contact> {
+        dispatch!(self, e => e.contact(otter))
+    }
+
+    fn on_contact(&mut self) {
+        dispatch!(self, e => e.on_contact())
+    }
+
+    fn aim(&mut self, otter: &Otter) {
+        dispatch!(self, e => e.aim(otter))
+    }
+
+    fn set_slowed(&mut self, slowed: bool) {
+        dispatch!(self, e => e.set_slowed(slowed))
+    }
+}
+
+impl From<Missile> for AnyEntity {
+    fn from(missile: Missile) -> Self {
+        AnyEntity::Missile(missile)
+    }
+}
+
+impl From<Coin> for AnyEntity {
+    fn from(coin: Coin) -> Self {
+        AnyEntity::Coin(coin)
+    }
+}
+
+impl From<Fish> for AnyEntity {
+    fn from(fish: Fish) -> Self {
+        AnyEntity::Fish(fish)
+    }
+}
+
+impl From<Rock> for AnyEntity {
+    fn from(rock: Rock) -> Self {
+        AnyEntity::Rock(rock)
+    }
+}
+
+impl From<Crab> for AnyEntity {
+    fn from(crab: Crab) -> Self {
+        AnyEntity::Crab(crab)
+    }
+}
+
+impl From<PowerUpPickup> for AnyEntity {
+    fn from(pickup: PowerUpPickup) -> Self {
+        AnyEntity::PowerUp(pickup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contacts_depend_on_state() {
+        let mut otter = Otter::new();
+        let mut missile = AnyEntity::from(Missile::new(100));
+        assert_eq!(missile.contact(&otter), Some(Contact::Fatal));
+        otter.activate_invincibility();
+        assert_eq!(
+            missile.contact(&otter),
+            Some(Contact::Destroyed {
+                score: MISSILE_DESTROY_SCORE
+            })
+        );
+        missile.on_contact();
+        assert_eq!(missile.contact(&otter), None);
+        assert!(missile.is_active());
+
+        let mut coin = AnyEntity::from(Coin::new(100));
+        assert_eq!(coin.contact(&otter), Some(Contact::Coin));
+        coin.on_contact();
+        assert_eq!(coin.contact(&otter), None);
+        assert!(!coin.is_active());
+    }
+
+    #[test]
+    fn test_seabed_hazard_contacts() {
+        let mut otter = Otter::new();
+        let rock = AnyEntity::from(Rock::new());
+        let crab = AnyEntity::from(Crab::new());
+        assert_eq!(rock.contact(&otter), Some(Contact::Fatal));
+        assert_eq!(crab.contact(&otter), None);
+
+        otter.motion.y = OTTER_WALK_Y;
+        otter.update();
+        assert_eq!(otter.state, OtterState::Walking);
+        assert_eq!(crab.contact(&otter), Some(Contact::Fatal));
+
+        otter.activate_invincibility();
+        assert_eq!(rock.contact(&otter), None);
+        assert_eq!(crab.contact(&otter), None);
+    }
+
+    #[test]
+    fn test_dispatch_matches_wrapped_entity() {
+        let fish = Fish::new(120);
+        let entity = AnyEntity::from(fish.clone());
+        assert_eq!(entity.as_fish(), Some(&fish));
+        assert_eq!(entity.as_missile(), None);
+        assert_eq!(entity.texture(), TextureId::Fish);
+        assert_eq!(entity.get_collision_rect(), fish.get_collision_rect());
+        assert_eq!(entity.sprite().clip, fish.get_clip());
+    }
+}
@@ -0,0 +1,124 @@
+//! Gameplay events
+//!
+//! `Game` queues a `GameEvent` for everything noteworthy that happens during
+//! a tick. Frontends drain the queue with `Game::take_events` and react to
+//! whichever events they care about: the SDL frontend plays each event's
+//! `sound`, and particles, analytics or achievements can hook in the same
+//! way without reading `Game` internals every frame.
+
+use crate::*;
+
+/// What ended a run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeathCause {
+    Missile,
+    Rock,
+    Crab,
+}
+
+impl DeathCause {
+    /// The hazard behind a fatal contact with `entity`
+    pub fn of(entity: &AnyEntity) -> Option<Self> {
+        match entity {
+            AnyEntity::Missile(_) => Some(DeathCause::Missile),
+            AnyEntity::Rock(_) => Some(DeathCause::Rock),
+            AnyEntity::Crab(_) => Some(DeathCause::Crab),
+            AnyEntity::Coin(_) | AnyEntity::Fish(_) | AnyEntity::PowerUp(_) => None,
+        }
+    }
+
+    /// How the run ended, as shown on the game over screen
+    pub fn description(&self) -> &'static str {
+        match self {
+            DeathCause::Missile => "HIT BY A MISSILE",
+            DeathCause::Rock => "CRASHED INTO A ROCK",
+            DeathCause::Crab => "NIPPED BY A CRAB",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameEvent {
+    /// A coin worth `value` (after the combo multiplier) was picked up,
+    /// centered at (x, y)
+    CoinCollected {
+        x: i32,
+        y: i32,
+        value: u32,
+    },
+    /// The invincible otter rammed a missile at (x, y) for `score`
+    MissileDestroyed {
+        x: i32,
+        y: i32,
+        score: u32,
+    },
+    /// A missile grazed past the otter for `score`
+    NearMiss {
+        score: u32,
+    },
+    PowerUpStarted(PowerUp),
+    /// Ran out, or was used up like a shield taking a hit
+    PowerUpEnded(PowerUp),
+    /// A shield saved the otter from `cause`
+    HitAbsorbed {
+        cause: DeathCause,
+    },
+    /// The otter touched down on the seabed and started walking
+    OtterLanded,
+    /// The run's score passed the previous high score of `previous`
+    HighScoreBeaten {
+        previous: u32,
+    },
+    GameOver {
+        score: u32,
+        cause: DeathCause,
+    },
+}
+
+impl GameEvent {
+    /// Sound the frontend should play for this event, if any
+    pub fn sound(&self) -> Option<SoundEffect> {
+        match self {
+            GameEvent::CoinCollected { .. } => Some(SoundEffect::Coin),
+            GameEvent::MissileDestroyed { .. }
+            | GameEvent::HitAbsorbed { .. }
+            | GameEvent::GameOver { .. } => Some(SoundEffect::Boom),
+            GameEvent::NearMiss { .. } => Some(SoundEffect::NearMiss),
+            GameEvent::PowerUpStarted(power_up) => Some(power_up.sound()),
+            GameEvent::PowerUpEnded(_)
+            | GameEvent::OtterLanded
+            | GameEvent::HighScoreBeaten { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_sounds() {
+        let coin = GameEvent::CoinCollected {
+            x: 0,
+            y: 0,
+            value: COIN_SCORE,
+        };
+        assert_eq!(coin.sound(), Some(SoundEffect::Coin));
+        assert_eq!(
+            GameEvent::PowerUpStarted(PowerUp::Magnet).sound(),
+            Some(SoundEffect::Magnet)
+        );
+        assert_eq!(GameEvent::PowerUpEnded(PowerUp::Magnet).sound(), None);
+        assert_eq!(GameEvent::OtterLanded.sound(), None);
+    }
+
+    #[test]
+    fn test_death_causes() {
+        assert_eq!(
+            DeathCause::of(&Missile::new(100).into()),
+            Some(DeathCause::Missile)
+        );
+        assert_eq!(DeathCause::of(&Crab::new().into()), Some(DeathCause::Crab));
+        assert_eq!(DeathCause::of(&Coin::new(100).into()), None);
+    }
+}
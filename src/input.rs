@@ -0,0 +1,125 @@
+//! Abstract player input
+//!
+//! Frontends map their physical inputs (keys, mouse buttons, gamepad
+//! buttons, touches) to `InputAction`s and report presses and releases
+//! through `Game::handle_action`. Which physical input triggers which
+//! action is up to the frontend.
+
+use crate::leaderboard::InitialsInput;
+use crate::{Game, GameState};
+
+/// Something the player can ask the game to do
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    /// Swim up while held; also starts a run, and during initials entry a
+    /// tap cycles the letter and a hold confirms it
+    Swim,
+    /// Pause or resume a run
+    Pause,
+    /// Start a run from the menu or game over screen, pause or resume
+    /// during one, and confirm initials
+    Start,
+    /// Show or hide the high score table
+    Leaderboard,
+    /// Leave the game (handled by the frontend, ignored by `Game`)
+    Quit,
+}
+
+impl InputAction {
+    pub const ALL: &'static [InputAction] = &[
+        InputAction::Swim,
+        InputAction::Pause,
+        InputAction::Start,
+        InputAction::Leaderboard,
+        InputAction::Quit,
+    ];
+
+    /// Lowercase name, as used in settings files
+    pub fn name(self) -> &'static str {
+        match self {
+            InputAction::Swim => "swim",
+            InputAction::Pause => "pause",
+            InputAction::Start => "start",
+            InputAction::Leaderboard => "leaderboard",
+            InputAction::Quit => "quit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|action| action.name() == name)
+    }
+}
+
+impl Game {
+    /// Whether the next `Swim` or `Start` press begins a new run
+    pub fn starts_run_on_press(&self) -> bool {
+        matches!(self.state, GameState::Menu | GameState::GameOver) && !self.is_entering_initials()
+    }
+
+    /// Apply a press (`pressed = true`) or release of an action
+    ///
+    /// Only `Swim` cares about releases; every other action fires on press.
+    pub fn handle_action(&mut self, action: InputAction, pressed: bool) {
+        match (action, pressed) {
+            (InputAction::Swim, true) => self.handle_space_pressed(),
+            (InputAction::Swim, false) => self.handle_space_released(),
+            (InputAction::Pause, true) => self.toggle_pause(),
+            (InputAction::Start, true) => match self.state {
+                GameState::GameOver if self.is_entering_initials() => {
+                    self.handle_initials_input(InitialsInput::Confirm);
+                }
+                GameState::Menu | GameState::GameOver => self.start(),
+                GameState::Playing | GameState::Paused => self.toggle_pause(),
+            },
+            (InputAction::Leaderboard, true) => self.toggle_leaderboard(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_starts_and_pauses() {
+        let mut game = Game::with_seed(0);
+        assert!(game.starts_run_on_press());
+        game.handle_action(InputAction::Start, true);
+        game.handle_action(InputAction::Start, false);
+        assert_eq!(game.state, GameState::Playing);
+
+        game.handle_action(InputAction::Start, true);
+        assert_eq!(game.state, GameState::Paused);
+        game.handle_action(InputAction::Pause, true);
+        assert_eq!(game.state, GameState::Playing);
+        assert!(!game.starts_run_on_press());
+    }
+
+    #[test]
+    fn test_swim_press_and_release() {
+        let mut game = Game::with_seed(0);
+        game.handle_action(InputAction::Swim, true);
+        assert_eq!(game.state, GameState::Playing);
+        game.handle_action(InputAction::Swim, false);
+        game.handle_action(InputAction::Swim, true);
+        assert!(game.otter.velocity_y < 0);
+        game.handle_action(InputAction::Swim, false);
+        assert!(game.otter.velocity_y > 0);
+    }
+
+    #[test]
+    fn test_quit_is_ignored() {
+        let mut game = Game::with_seed(0);
+        game.handle_action(InputAction::Quit, true);
+        assert_eq!(game.state, GameState::Menu);
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for &action in InputAction::ALL {
+            assert_eq!(InputAction::from_name(action.name()), Some(action));
+        }
+        assert_eq!(InputAction::from_name("jump"), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Local top-10 leaderboard with arcade-style initials entry
+//!
+//! After a run that makes the table, `Game` stays in `GameOver` with an
+//! `InitialsEntry` in progress until the player confirms three letters.
+//! With only one button, tapping it cycles the current letter and holding
+//! it for `INITIALS_HOLD_FRAMES` confirms it.
+
+use crate::{Game, GameState};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of runs kept on the leaderboard
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// Number of letters in a set of initials
+pub const INITIALS_LEN: usize = 3;
+
+/// Frames the one button must be held to confirm a letter (1 second)
+pub const INITIALS_HOLD_FRAMES: u32 = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub initials: String,
+    pub score: u32,
+    /// Date the run finished, as YYYY-MM-DD (UTC)
+    pub date: String,
+    /// Seed of the run, so it can be replayed with `--seed`
+    #[serde(with = "seed_string")]
+    pub seed: u64,
+}
+
+/// Best runs, highest score first
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Build a table from arbitrary entries, sorting and trimming them
+    pub fn from_entries(mut entries: Vec<LeaderboardEntry>) -> Self {
+        entries.sort_by_key(|e| Reverse(e.score));
+        entries.truncate(LEADERBOARD_SIZE);
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    /// Whether a run with this score would make the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < LEADERBOARD_SIZE
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Insert an entry below any existing equal scores
+    /// Returns its zero-based rank, or None if it didn't make the table
+    pub fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|e| entry.score > e.score)
+            .unwrap_or(self.entries.len());
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+}
+
+/// Inputs accepted while entering initials
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitialsInput {
+    /// Cycle the current letter forward (A -> B, Z -> A)
+    Next,
+    /// Cycle the current letter backward
+    Prev,
+    /// Move back to the previous letter
+    Back,
+    /// Lock in the current letter and move on
+    Confirm,
+    /// Type a letter directly and move on
+    Letter(char),
+}
+
+/// Initials being entered, one letter at a time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitialsEntry {
+    pub letters: [u8; INITIALS_LEN],
+    /// Index of the letter being edited
+    pub cursor: usize,
+    /// Frames the one button has been held, while it's down and hasn't
+    /// confirmed a letter yet
+    pub held_frames: Option<u32>,
+}
+
+impl InitialsEntry {
+    pub fn new() -> Self {
+        Self {
+            letters: [b'A'; INITIALS_LEN],
+            cursor: 0,
+            held_frames: None,
+        }
+    }
+
+    /// Apply an input; returns true once the last letter is confirmed
+    pub fn apply(&mut self, input: InitialsInput) -> bool {
+        let letter = &mut self.letters[self.cursor];
+        match input {
+            InitialsInput::Next => *letter = if *letter == b'Z' { b'A' } else { *letter + 1 },
+            InitialsInput::Prev => *letter = if *letter == b'A' { b'Z' } else { *letter - 1 },
+            InitialsInput::Back => self.cursor = self.cursor.saturating_sub(1),
+            InitialsInput::Confirm => return self.advance(),
+            InitialsInput::Letter(c) => {
+                if c.is_ascii_alphabetic() {
+                    *letter = c.to_ascii_uppercase() as u8;
+                    return self.advance();
+                }
+            }
+        }
+        false
+    }
+
+    /// The one button went down; what it does depends on how long it's held
+    pub fn press(&mut self) {
+        self.held_frames = Some(0);
+    }
+
+    /// The one button came up: a tap cycles the letter, while a hold has
+    /// already confirmed it
+    pub fn release(&mut self) -> Option<InitialsInput> {
+        self.held_frames.take().map(|_| InitialsInput::Next)
+    }
+
+    /// Count one frame of the button being held; confirms the letter once
+    /// it's been held for `INITIALS_HOLD_FRAMES`
+    pub fn tick(&mut self) -> Option<InitialsInput> {
+        let frames = self.held_frames.as_mut()?;
+        *frames += 1;
+        if *frames < INITIALS_HOLD_FRAMES {
+            return None;
+        }
+        self.held_frames = None;
+        Some(InitialsInput::Confirm)
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.cursor + 1 == INITIALS_LEN {
+            return true;
+        }
+        self.cursor += 1;
+        false
+    }
+
+    pub fn initials(&self) -> String {
+        self.letters.iter().map(|&b| b as char).collect()
+    }
+}
+
+impl Default for InitialsEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn is_entering_initials(&self) -> bool {
+        self.initials_entry.is_some()
+    }
+
+    /// Feed an input to the initials entry, if one is in progress
+    /// Returns the new entry's rank once the initials are submitted
+    pub fn handle_initials_input(&mut self, input: InitialsInput) -> Option<usize> {
+        let entry = self.initials_entry.as_mut()?;
+        if !entry.apply(input) {
+            return None;
+        }
+
+        let initials = entry.initials();
+        self.initials_entry = None;
+        self.last_rank = self.leaderboard.insert(LeaderboardEntry {
+            initials,
+            score: self.score,
+            date: today(),
+            seed: self.seed(),
+        });
+        self.last_rank
+    }
+
+    /// Count a frame of the one button being held during initials entry
+    pub(crate) fn tick_initials_hold(&mut self) {
+        if let Some(input) = self.initials_entry.as_mut().and_then(InitialsEntry::tick) {
+            self.handle_initials_input(input);
+        }
+    }
+
+    /// Switch the menu and game over screens between their normal view and the table
+    pub fn toggle_leaderboard(&mut self) {
+        let on_screen = matches!(self.state, GameState::Menu | GameState::GameOver);
+        if on_screen && !self.is_entering_initials() {
+            self.show_leaderboard = !self.show_leaderboard;
+        }
+    }
+}
+
+/// Today's date (UTC) as YYYY-MM-DD
+pub fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (y, m, d) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) date
+/// (Howard Hinnant's civil_from_days algorithm)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+/// TOML integers are signed 64-bit, so seeds are stored as strings
+mod seed_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&seed.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            initials: initials.to_string(),
+            score,
+            date: "2024-01-01".to_string(),
+            seed: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn test_insert_keeps_order_and_size() {
+        let mut board = Leaderboard::default();
+        for i in 0..LEADERBOARD_SIZE as u32 {
+            assert!(board.qualifies(100 + i));
+            board.insert(entry("AAA", 100 + i));
+        }
+        assert_eq!(board.entries().len(), LEADERBOARD_SIZE);
+        assert_eq!(board.entries()[0].score, 109);
+
+        assert!(!board.qualifies(100));
+        assert_eq!(board.insert(entry("LOW", 50)), None);
+        assert_eq!(board.insert(entry("TOP", 500)), Some(0));
+        assert_eq!(board.entries().len(), LEADERBOARD_SIZE);
+        assert_eq!(board.entries().last().unwrap().score, 101);
+    }
+
+    #[test]
+    fn test_ties_rank_below_existing() {
+        let mut board = Leaderboard::from_entries(vec![entry("OLD", 300), entry("ONE", 100)]);
+        assert_eq!(board.insert(entry("NEW", 300)), Some(1));
+        assert_eq!(board.entries()[0].initials, "OLD");
+    }
+
+    #[test]
+    fn test_initials_entry() {
+        let mut initials = InitialsEntry::new();
+        assert!(!initials.apply(InitialsInput::Prev)); // A -> Z
+        assert!(!initials.apply(InitialsInput::Confirm));
+        assert!(!initials.apply(InitialsInput::Letter('q')));
+        assert!(!initials.apply(InitialsInput::Back));
+        assert!(!initials.apply(InitialsInput::Next)); // Q -> R
+        assert!(!initials.apply(InitialsInput::Confirm));
+        assert!(!initials.apply(InitialsInput::Letter('1'))); // ignored
+        assert!(initials.apply(InitialsInput::Confirm));
+        assert_eq!(initials.initials(), "ZRA");
+    }
+
+    #[test]
+    fn test_one_button_tap_and_hold() {
+        let mut initials = InitialsEntry::new();
+        initials.press();
+        assert_eq!(initials.tick(), None);
+        assert_eq!(initials.release(), Some(InitialsInput::Next));
+
+        initials.press();
+        for _ in 1..INITIALS_HOLD_FRAMES {
+            assert_eq!(initials.tick(), None);
+        }
+        assert_eq!(initials.tick(), Some(InitialsInput::Confirm));
+        // Letting go after a hold doesn't also cycle the letter
+        assert_eq!(initials.tick(), None);
+        assert_eq!(initials.release(), None);
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_seed_survives_toml() {
+        let board = Leaderboard::from_entries(vec![entry("MAX", 10)]);
+        #[derive(Serialize, Deserialize)]
+        struct Wrapper {
+            board: Leaderboard,
+        }
+        let text = toml::to_string(&Wrapper {
+            board: board.clone(),
+        })
+        .unwrap();
+        let back: Wrapper = toml::from_str(&text).unwrap();
+        assert_eq!(back.board, board);
+    }
+}
@@ -1,9 +1,35 @@
 //! Otter Swag - A Rust port of the classic game
 //!
 //! This module contains the core game logic, separated from rendering
-//! to allow for testing and different rendering backends.
-
-use rand::Rng;
+//! to allow for testing and different rendering backends. Backends draw
+//! each frame by executing `Game::render_commands` (see the `render` module).
+
+pub mod config;
+pub mod entity;
+pub mod event;
+pub mod input;
+pub mod leaderboard;
+pub mod mask;
+pub mod powerup;
+pub mod rect;
+pub mod render;
+pub mod replay;
+pub mod save;
+pub mod stats;
+
+use config::{GameConfig, OtterPhysics};
+pub use entity::{AnyEntity, Contact, Entity, Motion};
+pub use event::{DeathCause, GameEvent};
+use leaderboard::{InitialsEntry, Leaderboard};
+use mask::{CollisionMasks, Sprite};
+pub use powerup::{slowed_velocity, Expiry, PowerUp, PowerUpPickup, PowerUps};
+use powerup::{MAGNET_PULL, MAGNET_RADIUS};
+pub use rect::Rect;
+pub use stats::{LifetimeStats, RunStats};
+use render::TextureId;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
 
 // =============================================================================
 // Constants (from original C++ source)
@@ -20,6 +46,12 @@ pub const OTTER_START_X: i32 = 50;
 pub const OTTER_START_Y: i32 = -35;
 pub const OTTER_VELOCITY: i32 = 16; // width / 2
 
+// Smooth otter physics, in sub-pixels (1/256 px) per frame
+pub const SUBPIXELS_PER_PIXEL: i32 = 256;
+pub const OTTER_SINK_ACCELERATION: i32 = 4 * SUBPIXELS_PER_PIXEL; // Weight minus buoyancy
+pub const OTTER_THRUST: i32 = 10 * SUBPIXELS_PER_PIXEL; // Upward push while swimming up
+pub const OTTER_TERMINAL_VELOCITY: i32 = OTTER_VELOCITY * SUBPIXELS_PER_PIXEL;
+
 // Boundary constants
 pub const OTTER_MIN_Y: i32 = 20;
 pub const OTTER_WALK_Y: i32 = 280;
@@ -28,6 +60,18 @@ pub const OTTER_WALK_Y: i32 = 280;
 pub const MISSILE_VELOCITY_X: i32 = 20;
 pub const MISSILE_EXPLODE_FRAMES: usize = 13;
 
+// Missile kinds beyond the original straight flier, unlocked as the score rises
+pub const HOMING_MISSILE_FROM_SCORE: u32 = 2500;
+pub const WAVE_MISSILE_FROM_SCORE: u32 = 5000;
+pub const FAST_MISSILE_FROM_SCORE: u32 = 10000;
+pub const HOMING_MISSILE_DESTROY_SCORE: u32 = 300;
+pub const WAVE_MISSILE_DESTROY_SCORE: u32 = 300;
+pub const FAST_MISSILE_DESTROY_SCORE: u32 = 400;
+pub const HOMING_MISSILE_TURN: i32 = 3; // Most it moves towards the otter per frame
+pub const FAST_MISSILE_VELOCITY_X: i32 = 40;
+pub const FAST_MISSILE_WARNING_FRAMES: u32 = 10; // One second of warning
+pub const FAST_MISSILE_WARNING_X: i32 = SCREEN_WIDTH - 20; // Where the warning shows
+
 // Coin constants (from coin.cpp)
 pub const COIN_START_Y: i32 = -50;
 pub const COIN_VELOCITY_Y: i32 = 7;
@@ -40,20 +84,45 @@ pub const COIN_SCORE: u32 = 100;
 pub const SCORE_PER_FRAME: u32 = 7;        // Added every frame during gameplay
 pub const MISSILE_DESTROY_SCORE: u32 = 200; // Bonus for destroying missile while invincible
 pub const INVINCIBILITY_SCORE_DURATION: u32 = 2000; // Invincibility lasts until score increases by this
+pub const COMBO_WINDOW_FRAMES: u32 = 2 * FRAMES_PER_SECOND; // Time to grab the next coin and keep a combo going
+pub const COMBO_MAX_MULTIPLIER: u32 = 5; // Most a coin's score is multiplied by
+pub const NEAR_MISS_SCORE: u32 = 150; // Bonus for a missile grazing past the otter
+pub const NEAR_MISS_MARGIN: i32 = 6; // How close (in pixels) counts as grazing
 
 // Fish constants (from fish.cpp)
 pub const FISH_VELOCITY_X: i32 = 10;
 
+// Seabed hazards (not in the original game)
+pub const SEABED_Y: i32 = OTTER_WALK_Y + OTTER_HEIGHT; // Where the walking otter's feet are
+pub const SEABED_VELOCITY_X: i32 = 10; // Rocks scroll along with the seabed
+pub const CRAB_VELOCITY_X: i32 = 14; // Crabs scuttle a little faster
+
+// Power-up constants
+pub const POWER_UP_VELOCITY_X: i32 = 10;
+pub const MAGNET_FRAMES: u32 = 10 * FRAMES_PER_SECOND;
+pub const SHIELD_FRAMES: u32 = 20 * FRAMES_PER_SECOND; // Unless a hit uses it up first
+pub const SLOW_MOTION_FRAMES: u32 = 6 * FRAMES_PER_SECOND;
+
 // Spawn rates (from main.cpp)
 pub const COIN_SPAWN_CHANCE: i32 = 50;   // out of 1000 (5%)
 pub const FISH_SPAWN_CHANCE: i32 = 3;    // out of 1000 (0.3%)
+pub const ROCK_SPAWN_CHANCE: i32 = 8;    // out of 1000 (0.8%)
+pub const CRAB_SPAWN_CHANCE: i32 = 6;    // out of 1000 (0.6%)
+pub const POWER_UP_SPAWN_CHANCE: i32 = 4; // out of 1000 (0.4%)
+
+// Missile spawn interval in frames, shrinking as the score rises
+pub const OBSTACLE_SPAWN_RATE_START: f32 = 50.0;
+pub const OBSTACLE_SPAWN_RATE_MIN: f32 = 10.0;
+pub const OBSTACLE_SPAWN_RATE_SCORE_STEP: f32 = 100.0; // Points per frame of interval removed
+
+// These are the defaults for `config::GameConfig`, which is what `Game` reads
 
 // =============================================================================
 // Sprite Clip Data (from original .cpp files)
 // =============================================================================
 
 /// A simple rectangle for sprite clipping
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct ClipRect {
     pub x: i32,
     pub y: i32,
@@ -112,6 +181,19 @@ pub const MISSILE_CLIPS_EXPLODE: &[ClipRect] = &[
     ClipRect::new(13, 16, 1, 1), // Final frame (invisible)
 ];
 
+// Fast missile warning, the "!" in font.bmp
+pub const MISSILE_WARNING_CLIP: ClipRect = ClipRect::new(468, 0, 12, 16);
+
+// Vertical offsets of a wave missile from its center line, one per frame
+pub const WAVE_MISSILE_OFFSETS: &[i32] = &[
+    0, 12, 23, 30, 32, 30, 23, 12, 0, -12, -23, -30, -32, -30, -23, -12,
+];
+pub const WAVE_MISSILE_AMPLITUDE: i32 = 32;
+
+// Band of heights missiles are launched at
+pub const MISSILE_SPAWN_MIN_Y: i32 = 30;
+pub const MISSILE_SPAWN_MAX_Y: i32 = 280;
+
 // Coin sprite clips (from coin.cpp)
 pub const COIN_CLIPS_SPIN: &[ClipRect] = &[
     ClipRect::new(6, 3, 14, 16),
@@ -135,6 +217,14 @@ pub const FISH_CLIPS_SWIM: &[ClipRect] = &[
     ClipRect::new(364, 5, 30, 27),
 ];
 
+// Seabed hazard sprite clips (seabed.bmp), each resting on its clip's bottom edge
+pub const ROCK_CLIPS: &[ClipRect] = &[ClipRect::new(0, 8, 32, 24)];
+
+pub const CRAB_CLIPS_WALK: &[ClipRect] = &[
+    ClipRect::new(32, 12, 28, 17),
+    ClipRect::new(64, 12, 28, 17),
+];
+
 // Number sprite clips (from scoreCounter.cpp) - for score display
 // Each digit 0-9 from numbers.bmp
 pub const NUMBER_CLIPS: &[ClipRect] = &[
@@ -150,9 +240,89 @@ pub const NUMBER_CLIPS: &[ClipRect] = &[
     ClipRect::new(253, 0, 26, 28), // 9
 ];
 
+// Frames of countdown between unpausing and play resuming (3 seconds)
+pub const RESUME_COUNTDOWN_FRAMES: u32 = 3 * FRAMES_PER_SECOND;
+
 // Digit spacing for score display
 pub const DIGIT_SPACING: i32 = 28;
 
+// Font glyph clips (font.bmp) - small outlined text, extends the NUMBER_CLIPS
+// approach to letters for initials, the leaderboard and other screen text
+pub const LETTER_CLIPS: &[ClipRect] = &[
+    ClipRect::new(0, 0, 12, 16), // A
+    ClipRect::new(12, 0, 12, 16), // B
+    ClipRect::new(24, 0, 12, 16), // C
+    ClipRect::new(36, 0, 12, 16), // D
+    ClipRect::new(48, 0, 12, 16), // E
+    ClipRect::new(60, 0, 12, 16), // F
+    ClipRect::new(72, 0, 12, 16), // G
+    ClipRect::new(84, 0, 12, 16), // H
+    ClipRect::new(96, 0, 12, 16), // I
+    ClipRect::new(108, 0, 12, 16), // J
+    ClipRect::new(120, 0, 12, 16), // K
+    ClipRect::new(132, 0, 12, 16), // L
+    ClipRect::new(144, 0, 12, 16), // M
+    ClipRect::new(156, 0, 12, 16), // N
+    ClipRect::new(168, 0, 12, 16), // O
+    ClipRect::new(180, 0, 12, 16), // P
+    ClipRect::new(192, 0, 12, 16), // Q
+    ClipRect::new(204, 0, 12, 16), // R
+    ClipRect::new(216, 0, 12, 16), // S
+    ClipRect::new(228, 0, 12, 16), // T
+    ClipRect::new(240, 0, 12, 16), // U
+    ClipRect::new(252, 0, 12, 16), // V
+    ClipRect::new(264, 0, 12, 16), // W
+    ClipRect::new(276, 0, 12, 16), // X
+    ClipRect::new(288, 0, 12, 16), // Y
+    ClipRect::new(300, 0, 12, 16), // Z
+];
+
+pub const FONT_DIGIT_CLIPS: &[ClipRect] = &[
+    ClipRect::new(312, 0, 12, 16), // 0
+    ClipRect::new(324, 0, 12, 16), // 1
+    ClipRect::new(336, 0, 12, 16), // 2
+    ClipRect::new(348, 0, 12, 16), // 3
+    ClipRect::new(360, 0, 12, 16), // 4
+    ClipRect::new(372, 0, 12, 16), // 5
+    ClipRect::new(384, 0, 12, 16), // 6
+    ClipRect::new(396, 0, 12, 16), // 7
+    ClipRect::new(408, 0, 12, 16), // 8
+    ClipRect::new(420, 0, 12, 16), // 9
+];
+
+/// Punctuation available in font.bmp, in the same order as FONT_SYMBOL_CLIPS
+pub const FONT_SYMBOLS: &str = "-.:!/^+#?%<>";
+
+pub const FONT_SYMBOL_CLIPS: &[ClipRect] = &[
+    ClipRect::new(432, 0, 12, 16), // -
+    ClipRect::new(444, 0, 12, 16), // .
+    ClipRect::new(456, 0, 12, 16), // :
+    ClipRect::new(468, 0, 12, 16), // !
+    ClipRect::new(480, 0, 12, 16), // /
+    ClipRect::new(492, 0, 12, 16), // ^
+    ClipRect::new(504, 0, 12, 16), // +
+    ClipRect::new(516, 0, 12, 16), // #
+    ClipRect::new(528, 0, 12, 16), // ?
+    ClipRect::new(540, 0, 12, 16), // %
+    ClipRect::new(552, 0, 12, 16), // <
+    ClipRect::new(564, 0, 12, 16), // >
+];
+
+// Glyph advance and height for font text
+pub const GLYPH_SPACING: i32 = 12;
+pub const FONT_GLYPH_HEIGHT: u32 = 16;
+
+/// Font clip for a character (letters are case-insensitive); None for
+/// characters the font doesn't have, which are drawn as blank space
+pub fn glyph_clip(c: char) -> Option<ClipRect> {
+    let c = c.to_ascii_uppercase();
+    match c {
+        'A'..='Z' => Some(LETTER_CLIPS[(c as u8 - b'A') as usize]),
+        '0'..='9' => Some(FONT_DIGIT_CLIPS[(c as u8 - b'0') as usize]),
+        _ => FONT_SYMBOLS.find(c).map(|i| FONT_SYMBOL_CLIPS[i]),
+    }
+}
+
 // =============================================================================
 // Game Types
 // =============================================================================
@@ -161,6 +331,8 @@ pub const DIGIT_SPACING: i32 = 28;
 pub enum GameState {
     Menu,
     Playing,
+    /// Run frozen mid-play; resumes through a countdown
+    Paused,
     GameOver,
 }
 
@@ -189,42 +361,123 @@ pub enum MissileState {
     Exploding,
 }
 
-/// Sound effect events that the renderer should play
+/// How a missile flies; every kind but `Straight` is unlocked by score
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MissileKind {
+    /// Straight across, as in the original
+    Straight,
+    /// Drifts towards the otter's height
+    Homing,
+    /// Weaves up and down around its launch height
+    Wave,
+    /// Much faster, after a warning at the screen edge
+    Fast,
+}
+
+/// Sounds the frontend plays for `GameEvent`s
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SoundEffect {
     Coin,
     Powerup,
     Boom,
+    Magnet,
+    Shield,
+    SlowMotion,
+    NearMiss,
+}
+
+impl SoundEffect {
+    pub const ALL: &'static [SoundEffect] = &[
+        SoundEffect::Coin,
+        SoundEffect::Powerup,
+        SoundEffect::Boom,
+        SoundEffect::Magnet,
+        SoundEffect::Shield,
+        SoundEffect::SlowMotion,
+        SoundEffect::NearMiss,
+    ];
+
+    /// Path of the sound file, relative to the working directory
+    pub fn asset_path(&self) -> &'static str {
+        match self {
+            SoundEffect::Coin => "assets/sounds/coin.wav",
+            SoundEffect::Powerup => "assets/sounds/powerup.wav",
+            SoundEffect::Boom => "assets/sounds/boom.wav",
+            SoundEffect::Magnet => "assets/sounds/magnet.wav",
+            SoundEffect::Shield => "assets/sounds/shield.wav",
+            SoundEffect::SlowMotion => "assets/sounds/slowmo.wav",
+            SoundEffect::NearMiss => "assets/sounds/nearmiss.wav",
+        }
+    }
 }
 
 // =============================================================================
 // Game Entities
 // =============================================================================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Otter {
-    pub x: i32,
-    pub y: i32,
+    pub motion: Motion,
     pub velocity_y: i32,
     pub state: OtterState,
     pub frame: usize,
     pub is_invincible: bool,
+    /// Vertical swim speed in either direction
+    pub speed: i32,
+    pub physics: OtterPhysics,
+    /// Smooth physics state: `y` and `velocity_y` in sub-pixels, and
+    /// whether the otter is pushing upwards
+    pub sub_y: i32,
+    pub sub_velocity_y: i32,
+    pub thrusting: bool,
+    // Smooth physics tuning, in sub-pixels per frame
+    pub sink_acceleration: i32,
+    pub thrust: i32,
+    pub terminal_velocity: i32,
 }
 
 impl Otter {
     pub fn new() -> Self {
+        Self::with_speed(OTTER_VELOCITY)
+    }
+
+    pub fn with_speed(speed: i32) -> Self {
         Self {
-            x: OTTER_START_X,
-            y: OTTER_START_Y,
-            velocity_y: OTTER_VELOCITY,
+            motion: Motion::at(OTTER_START_X, OTTER_START_Y),
+            velocity_y: speed,
             state: OtterState::SwimmingDown,
             frame: 0,
             is_invincible: false,
+            speed,
+            physics: OtterPhysics::Classic,
+            sub_y: OTTER_START_Y * SUBPIXELS_PER_PIXEL,
+            sub_velocity_y: 0,
+            thrusting: false,
+            sink_acceleration: OTTER_SINK_ACCELERATION,
+            thrust: OTTER_THRUST,
+            terminal_velocity: OTTER_TERMINAL_VELOCITY,
+        }
+    }
+
+    /// An otter moving the way `config` asks for
+    pub fn from_config(config: &GameConfig) -> Self {
+        Self {
+            physics: config.otter_physics,
+            sink_acceleration: config.otter_sink_acceleration,
+            thrust: config.otter_thrust,
+            terminal_velocity: config.otter_terminal_velocity,
+            ..Self::with_speed(config.otter_velocity)
         }
     }
 
     pub fn reset(&mut self) {
-        *self = Self::new();
+        *self = Self {
+            physics: self.physics,
+            sink_acceleration: self.sink_acceleration,
+            thrust: self.thrust,
+            terminal_velocity: self.terminal_velocity,
+            ..Self::with_speed(self.speed)
+        };
     }
 
     fn set_state(&mut self, new_state: OtterState) {
@@ -235,7 +488,10 @@ impl Otter {
     }
 
     pub fn swim_up(&mut self) {
-        self.velocity_y = -OTTER_VELOCITY;
+        match self.physics {
+            OtterPhysics::Classic => self.velocity_y = -self.speed,
+            OtterPhysics::Smooth => self.thrusting = true,
+        }
         if self.is_invincible {
             self.set_state(OtterState::Rolling);
         } else {
@@ -244,7 +500,10 @@ impl Otter {
     }
 
     pub fn swim_down(&mut self) {
-        self.velocity_y = OTTER_VELOCITY;
+        match self.physics {
+            OtterPhysics::Classic => self.velocity_y = self.speed,
+            OtterPhysics::Smooth => self.thrusting = false,
+        }
         if self.is_invincible {
             self.set_state(OtterState::Rolling);
         } else {
@@ -253,24 +512,37 @@ impl Otter {
     }
 
     pub fn update(&mut self) {
+        self.hold_position();
+
         // Apply velocity
-        self.y += self.velocity_y;
+        match self.physics {
+            OtterPhysics::Classic => self.motion.y += self.velocity_y,
+            OtterPhysics::Smooth => self.apply_smooth_physics(),
+        }
 
         // Boundary checks (from otter.cpp)
-        if self.y > OTTER_WALK_Y {
+        if self.motion.y > OTTER_WALK_Y {
             // Only switch to Walking if not invincible (invincible stays Rolling)
             if !self.is_invincible {
                 self.set_state(OtterState::Walking);
             }
-            self.y = OTTER_WALK_Y;
+            self.motion.y = OTTER_WALK_Y;
         }
 
-        if self.y + OTTER_HEIGHT > SCREEN_HEIGHT {
-            self.y = SCREEN_HEIGHT - OTTER_HEIGHT;
+        if self.motion.y + OTTER_HEIGHT > SCREEN_HEIGHT {
+            self.motion.y = SCREEN_HEIGHT - OTTER_HEIGHT;
         }
 
-        if self.y < OTTER_MIN_Y {
-            self.y = OTTER_MIN_Y;
+        if self.motion.y < OTTER_MIN_Y {
+            self.motion.y = OTTER_MIN_Y;
+        }
+
+        // Hitting the seabed or the surface stops smooth movement dead
+        let sub_pixel_y = self.sub_y.div_euclid(SUBPIXELS_PER_PIXEL);
+        if self.physics == OtterPhysics::Smooth && self.motion.y != sub_pixel_y {
+            self.sub_y = self.motion.y * SUBPIXELS_PER_PIXEL;
+            self.sub_velocity_y = 0;
+            self.velocity_y = 0;
         }
 
         // Note: Invincibility is now managed by Game based on score, not timer
@@ -280,6 +552,25 @@ impl Otter {
         self.frame = (self.frame + 1) % clips.len();
     }
 
+    /// Accelerate towards terminal velocity, sinking unless thrusting up
+    fn apply_smooth_physics(&mut self) {
+        // Follow the otter if something moved it by whole pixels
+        if self.motion.y != self.sub_y.div_euclid(SUBPIXELS_PER_PIXEL) {
+            self.sub_y = self.motion.y * SUBPIXELS_PER_PIXEL;
+        }
+
+        let mut acceleration = self.sink_acceleration;
+        if self.thrusting {
+            acceleration -= self.thrust;
+        }
+        self.sub_velocity_y = (self.sub_velocity_y + acceleration)
+            .clamp(-self.terminal_velocity, self.terminal_velocity);
+        self.sub_y += self.sub_velocity_y;
+
+        self.motion.y = self.sub_y.div_euclid(SUBPIXELS_PER_PIXEL);
+        self.velocity_y = self.sub_velocity_y / SUBPIXELS_PER_PIXEL;
+    }
+
     pub fn activate_invincibility(&mut self) {
         self.is_invincible = true;
         self.set_state(OtterState::Rolling);
@@ -295,33 +586,38 @@ impl Otter {
         clips[self.frame % clips.len()] // Safety: modulo ensures valid index
     }
 
-    pub fn get_collision_rect(&self) -> (i32, i32, u32, u32) {
+    /// Make the previous position the current one, so interpolation stands still
+    pub fn hold_position(&mut self) {
+        self.motion.hold();
+    }
+
+    pub fn get_collision_rect(&self) -> Rect {
         // State and frame-dependent collision boxes (from otter.cpp check_collision)
         match self.state {
             OtterState::SwimmingUp => {
                 if self.frame == 0 {
                     // Top: y+7, Bottom: y+31, Left: x, Right: x+32
-                    (self.x, self.y + 7, 32, 24)
+                    Rect::new(self.motion.x, self.motion.y + 7, 32, 24)
                 } else {
                     // Top: y+3, Bottom: y+31, Left: x, Right: x+32
-                    (self.x, self.y + 3, 32, 28)
+                    Rect::new(self.motion.x, self.motion.y + 3, 32, 28)
                 }
             }
             OtterState::SwimmingDown => {
                 // Top: y+1, Bottom: y+31, Left: x, Right: x+32
-                (self.x, self.y + 1, 32, 30)
+                Rect::new(self.motion.x, self.motion.y + 1, 32, 30)
             }
             OtterState::Walking => {
                 // Top: y+13, Bottom: y+31, Left: x, Right: x+31
-                (self.x, self.y + 13, 31, 18)
+                Rect::new(self.motion.x, self.motion.y + 13, 31, 18)
             }
             OtterState::Rolling => {
                 if self.frame == 0 || self.frame == 2 {
                     // Top: y+6, Bottom: y+32, Left: x, Right: x+33
-                    (self.x, self.y + 6, 33, 26)
+                    Rect::new(self.motion.x, self.motion.y + 6, 33, 26)
                 } else {
                     // Top: y, Bottom: y+32, Left: x, Right: x+26
-                    (self.x, self.y, 26, 32)
+                    Rect::new(self.motion.x, self.motion.y, 26, 32)
                 }
             }
         }
@@ -334,33 +630,134 @@ impl Default for Otter {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Missile {
-    pub x: i32,
-    pub y: i32,
+    pub motion: Motion,
+    pub velocity_x: i32,
+    pub kind: MissileKind,
     pub state: MissileState,
     pub frame: usize,
     pub active: bool,
+    /// Frames spent flying, which drives the wave motion
+    pub age: usize,
+    /// Height a wave missile weaves around
+    pub base_y: i32,
+    /// Height a homing missile steers its center towards
+    pub target_y: i32,
+    /// Most a homing missile moves towards `target_y` per frame
+    pub turn: i32,
+    /// Frames left showing the warning before a fast missile launches
+    pub warning_frames: u32,
+    /// Bonus for ramming it while invincible
+    pub destroy_score: u32,
+    /// Flying at reduced speed during slow motion
+    pub slowed: bool,
+    /// Came within the near-miss margin of the otter without touching it
+    pub grazed: bool,
+    /// Flew on past after grazing the otter, and was scored for it
+    pub near_miss_scored: bool,
 }
 
 impl Missile {
     pub fn new(y: i32) -> Self {
+        Self::with_velocity(y, MISSILE_VELOCITY_X)
+    }
+
+    pub fn with_velocity(y: i32, velocity_x: i32) -> Self {
         Self {
-            x: SCREEN_WIDTH,
-            y,
+            motion: Motion::at(SCREEN_WIDTH, y),
+            velocity_x,
+            kind: MissileKind::Straight,
             state: MissileState::Shooting,
             frame: 0,
             active: true,
+            age: 0,
+            base_y: y,
+            target_y: y,
+            turn: HOMING_MISSILE_TURN,
+            warning_frames: 0,
+            destroy_score: MISSILE_DESTROY_SCORE,
+            slowed: false,
+            grazed: false,
+            near_miss_scored: false,
         }
     }
 
-    pub fn update(&mut self) {
+    /// A missile of `kind` launched at height `y`, tuned by `config`
+    pub fn of_kind(kind: MissileKind, y: i32, config: &GameConfig) -> Self {
+        let (velocity_x, destroy_score) = match kind {
+            MissileKind::Straight => (config.missile_velocity_x, config.missile_destroy_score),
+            MissileKind::Homing => (config.missile_velocity_x, config.homing_missile_destroy_score),
+            MissileKind::Wave => (config.missile_velocity_x, config.wave_missile_destroy_score),
+            MissileKind::Fast => (config.fast_missile_velocity_x, config.fast_missile_destroy_score),
+        };
+        let mut missile = Self {
+            kind,
+            destroy_score,
+            turn: config.homing_missile_turn,
+            ..Self::with_velocity(y, velocity_x)
+        };
+
+        match kind {
+            MissileKind::Wave => {
+                // Keep the whole wave inside the missile spawn band
+                missile.base_y = y.clamp(
+                    MISSILE_SPAWN_MIN_Y + WAVE_MISSILE_AMPLITUDE,
+                    MISSILE_SPAWN_MAX_Y - WAVE_MISSILE_AMPLITUDE,
+                );
+                missile.motion.y = missile.base_y;
+            }
+            MissileKind::Fast if config.fast_missile_warning_frames > 0 => {
+                missile.warning_frames = config.fast_missile_warning_frames;
+                missile.motion.x = FAST_MISSILE_WARNING_X;
+            }
+            _ => {}
+        }
+        missile.hold_position();
+        missile
+    }
+
+    /// Whether it's still only a warning, not yet flying
+    pub fn is_warning(&self) -> bool {
+        self.warning_frames > 0
+    }
+
+    pub fn explode(&mut self) {
+        self.state = MissileState::Exploding;
+        self.frame = 0;
+    }
+}
+
+impl Entity for Missile {
+    fn update(&mut self) {
+        self.hold_position();
         match self.state {
+            MissileState::Shooting if self.is_warning() => {
+                self.warning_frames -= 1;
+                if !self.is_warning() {
+                    // Launch from the right edge without sliding there
+                    self.motion.x = SCREEN_WIDTH;
+                    self.hold_position();
+                }
+            }
             MissileState::Shooting => {
-                self.x -= MISSILE_VELOCITY_X;
+                self.motion.x -= slowed_velocity(self.velocity_x, self.slowed);
                 self.frame = (self.frame + 1) % MISSILE_CLIPS_SHOOT.len();
+                self.age += 1;
 
-                if self.x < -50 {
+                match self.kind {
+                    MissileKind::Homing => {
+                        let center_y = self.get_collision_rect().center().1;
+                        self.motion.y += (self.target_y - center_y).clamp(-self.turn, self.turn);
+                    }
+                    MissileKind::Wave => {
+                        self.motion.y = self.base_y
+                            + WAVE_MISSILE_OFFSETS[self.age % WAVE_MISSILE_OFFSETS.len()];
+                    }
+                    MissileKind::Straight | MissileKind::Fast => {}
+                }
+
+                if self.motion.x < -50 {
                     self.active = false;
                 }
             }
@@ -373,13 +770,10 @@ impl Missile {
         }
     }
 
-    pub fn explode(&mut self) {
-        self.state = MissileState::Exploding;
-        self.frame = 0;
-    }
-
-    pub fn get_clip(&self) -> ClipRect {
+    fn get_clip(&self) -> ClipRect {
         match self.state {
+            // A "!" at the screen edge
+            MissileState::Shooting if self.is_warning() => MISSILE_WARNING_CLIP,
             MissileState::Shooting => {
                 MISSILE_CLIPS_SHOOT[self.frame % MISSILE_CLIPS_SHOOT.len()]
             }
@@ -390,94 +784,403 @@ impl Missile {
         }
     }
 
-    pub fn get_collision_rect(&self) -> (i32, i32, u32, u32) {
-        // From otter.cpp: obstacle hitbox is 24x10
-        (self.x, self.y, 24, 10)
+    fn get_collision_rect(&self) -> Rect {
+        match self.kind {
+            // From otter.cpp: obstacle hitbox is 24x10
+            MissileKind::Straight => Rect::new(self.motion.x, self.motion.y, 24, 10),
+            // A little more forgiving, since it follows the otter
+            MissileKind::Homing => Rect::new(self.motion.x + 2, self.motion.y + 1, 20, 8),
+            MissileKind::Wave => Rect::new(self.motion.x, self.motion.y + 1, 22, 8),
+            // The whole sprite, motion blur and all
+            MissileKind::Fast => Rect::new(self.motion.x, self.motion.y, 32, 10),
+        }
+    }
+
+    fn texture(&self) -> TextureId {
+        if self.is_warning() {
+            TextureId::Font
+        } else {
+            TextureId::Missile
+        }
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn depth(&self) -> i32 {
+        2
+    }
+
+    fn contact(&self, otter: &Otter) -> Option<Contact> {
+        match self.state {
+            MissileState::Shooting if self.is_warning() => None,
+            MissileState::Shooting if otter.is_invincible => Some(Contact::Destroyed {
+                score: self.destroy_score,
+            }),
+            MissileState::Shooting => Some(Contact::Fatal),
+            MissileState::Exploding => None,
+        }
+    }
+
+    fn on_contact(&mut self) {
+        self.explode();
+    }
+
+    fn aim(&mut self, otter: &Otter) {
+        if self.kind == MissileKind::Homing {
+            self.target_y = otter.get_collision_rect().center().1;
+        }
+    }
+
+    fn set_slowed(&mut self, slowed: bool) {
+        self.slowed = slowed;
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Coin {
-    pub x: i32,
-    pub y: i32,
+    pub motion: Motion,
     pub velocity_x: i32,
+    pub velocity_y: i32,
+    /// Horizontal drift once the coin is underwater
+    pub water_velocity_x: i32,
     pub frame: usize,
     pub collected: bool,
     pub active: bool,
 }
 
 impl Coin {
+    /// Whether it sank past the bottom without being collected
+    pub fn is_missed(&self) -> bool {
+        !self.collected && self.motion.y > COIN_BOTTOM_THRESHOLD
+    }
+
     pub fn new(x: i32) -> Self {
+        Self::with_velocity(x, COIN_VELOCITY_Y, COIN_VELOCITY_X_WATER)
+    }
+
+    pub fn with_velocity(x: i32, velocity_y: i32, water_velocity_x: i32) -> Self {
         Self {
-            x,
-            y: COIN_START_Y,
+            motion: Motion::at(x, COIN_START_Y),
             velocity_x: 0,
+            velocity_y,
+            water_velocity_x,
             frame: 0,
             collected: false,
             active: true,
         }
     }
+}
 
-    pub fn update(&mut self) {
-        self.y += COIN_VELOCITY_Y;
+impl Entity for Coin {
+    fn update(&mut self) {
+        self.hold_position();
+        self.motion.y += self.velocity_y;
 
         // Water drift (from coin.cpp)
-        if self.y > COIN_WATER_THRESHOLD {
-            self.velocity_x = COIN_VELOCITY_X_WATER;
+        if self.motion.y > COIN_WATER_THRESHOLD {
+            self.velocity_x = self.water_velocity_x;
         }
-        self.x += self.velocity_x;
+        self.motion.x += self.velocity_x;
 
         // Animation
         self.frame = (self.frame + 1) % COIN_CLIPS_SPIN.len();
 
         // Deactivate at bottom or off-screen
-        if self.y > COIN_BOTTOM_THRESHOLD || self.x < -20 {
+        if self.motion.y > COIN_BOTTOM_THRESHOLD || self.motion.x < -20 {
             self.active = false;
         }
     }
 
-    pub fn get_clip(&self) -> ClipRect {
+    fn get_clip(&self) -> ClipRect {
         COIN_CLIPS_SPIN[self.frame % COIN_CLIPS_SPIN.len()]
     }
 
-    pub fn get_collision_rect(&self) -> (i32, i32, u32, u32) {
-        (self.x, self.y, 16, 16)
+    fn get_collision_rect(&self) -> Rect {
+        Rect::new(self.motion.x, self.motion.y, 16, 16)
+    }
+
+    fn texture(&self) -> TextureId {
+        TextureId::Coin
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active && !self.collected
+    }
+
+    fn depth(&self) -> i32 {
+        0
+    }
+
+    fn contact(&self, _otter: &Otter) -> Option<Contact> {
+        (!self.collected).then_some(Contact::Coin)
+    }
+
+    fn on_contact(&mut self) {
+        self.collected = true;
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Fish {
-    pub x: i32,
-    pub y: i32,
+    pub motion: Motion,
+    pub velocity_x: i32,
     pub frame: usize,
     pub active: bool,
 }
 
 impl Fish {
     pub fn new(y: i32) -> Self {
+        Self::with_velocity(y, FISH_VELOCITY_X)
+    }
+
+    pub fn with_velocity(y: i32, velocity_x: i32) -> Self {
         Self {
-            x: SCREEN_WIDTH,
-            y,
+            motion: Motion::at(SCREEN_WIDTH, y),
+            velocity_x,
             frame: 0,
             active: true,
         }
     }
+}
 
-    pub fn update(&mut self) {
-        self.x -= FISH_VELOCITY_X;
+impl Entity for Fish {
+    fn update(&mut self) {
+        self.hold_position();
+        self.motion.x -= self.velocity_x;
         self.frame = (self.frame + 1) % FISH_CLIPS_SWIM.len();
 
-        if self.x < -40 {
+        if self.motion.x < -40 {
             self.active = false;
         }
     }
 
-    pub fn get_clip(&self) -> ClipRect {
+    fn get_clip(&self) -> ClipRect {
         FISH_CLIPS_SWIM[self.frame % FISH_CLIPS_SWIM.len()]
     }
 
-    pub fn get_collision_rect(&self) -> (i32, i32, u32, u32) {
-        (self.x, self.y, 30, 20)
+    fn get_collision_rect(&self) -> Rect {
+        Rect::new(self.motion.x, self.motion.y, 30, 20)
+    }
+
+    fn texture(&self) -> TextureId {
+        TextureId::Fish
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn depth(&self) -> i32 {
+        1
+    }
+
+    fn contact(&self, _otter: &Otter) -> Option<Contact> {
+        self.active.then_some(Contact::PowerUp(PowerUp::Invincibility))
+    }
+
+    fn on_contact(&mut self) {
+        self.active = false;
+    }
+}
+
+/// A boulder on the seabed; only an otter on or near the floor can hit it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rock {
+    pub motion: Motion,
+    pub velocity_x: i32,
+    pub active: bool,
+    /// Scrolling at reduced speed during slow motion
+    pub slowed: bool,
+}
+
+impl Rock {
+    pub fn new() -> Self {
+        Self::with_velocity(SEABED_VELOCITY_X)
+    }
+
+    pub fn with_velocity(velocity_x: i32) -> Self {
+        let y = SEABED_Y - ROCK_CLIPS[0].h as i32;
+        Self {
+            motion: Motion::at(SCREEN_WIDTH, y),
+            velocity_x,
+            active: true,
+            slowed: false,
+        }
+    }
+}
+
+impl Default for Rock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Entity for Rock {
+    fn update(&mut self) {
+        self.hold_position();
+        self.motion.x -= slowed_velocity(self.velocity_x, self.slowed);
+
+        if self.motion.x < -40 {
+            self.active = false;
+        }
+    }
+
+    fn get_clip(&self) -> ClipRect {
+        ROCK_CLIPS[0]
+    }
+
+    fn get_collision_rect(&self) -> Rect {
+        // Inset from the lumpy outline
+        Rect::new(self.motion.x + 2, self.motion.y + 4, 28, 20)
+    }
+
+    fn texture(&self) -> TextureId {
+        TextureId::Seabed
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn depth(&self) -> i32 {
+        -1
+    }
+
+    /// Deadly to hit, but a rolling (invincible) otter bounces over it
+    fn contact(&self, otter: &Otter) -> Option<Contact> {
+        (self.active && !otter.is_invincible).then_some(Contact::Fatal)
+    }
+
+    /// Knocked away by a shielded otter
+    fn on_contact(&mut self) {
+        self.active = false;
+    }
+
+    fn set_slowed(&mut self, slowed: bool) {
+        self.slowed = slowed;
+    }
+}
+
+/// A crab walking along the seabed; it nips a walking otter's feet
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crab {
+    pub motion: Motion,
+    pub velocity_x: i32,
+    pub frame: usize,
+    pub active: bool,
+    /// Walking at reduced speed during slow motion
+    pub slowed: bool,
+}
+
+impl Crab {
+    pub fn new() -> Self {
+        Self::with_velocity(CRAB_VELOCITY_X)
+    }
+
+    pub fn with_velocity(velocity_x: i32) -> Self {
+        let y = SEABED_Y - CRAB_CLIPS_WALK[0].h as i32;
+        Self {
+            motion: Motion::at(SCREEN_WIDTH, y),
+            velocity_x,
+            frame: 0,
+            active: true,
+            slowed: false,
+        }
+    }
+}
+
+impl Default for Crab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Entity for Crab {
+    fn update(&mut self) {
+        self.hold_position();
+        self.motion.x -= slowed_velocity(self.velocity_x, self.slowed);
+        self.frame = (self.frame + 1) % CRAB_CLIPS_WALK.len();
+
+        if self.motion.x < -40 {
+            self.active = false;
+        }
+    }
+
+    fn get_clip(&self) -> ClipRect {
+        CRAB_CLIPS_WALK[self.frame % CRAB_CLIPS_WALK.len()]
+    }
+
+    fn get_collision_rect(&self) -> Rect {
+        // Claws and shell, leaving out the legs
+        Rect::new(self.motion.x + 2, self.motion.y + 1, 24, 12)
+    }
+
+    fn texture(&self) -> TextureId {
+        TextureId::Seabed
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn depth(&self) -> i32 {
+        -1
+    }
+
+    /// Only dangerous to an otter walking on the seabed
+    fn contact(&self, otter: &Otter) -> Option<Contact> {
+        (self.active && otter.state == OtterState::Walking).then_some(Contact::Fatal)
+    }
+
+    /// Knocked away by a shielded otter
+    fn on_contact(&mut self) {
+        self.active = false;
+    }
+
+    fn set_slowed(&mut self, slowed: bool) {
+        self.slowed = slowed;
     }
 }
 
@@ -485,14 +1188,8 @@ impl Fish {
 // Collision Detection
 // =============================================================================
 
-pub fn rects_collide(a: (i32, i32, u32, u32), b: (i32, i32, u32, u32)) -> bool {
-    let (ax, ay, aw, ah) = a;
-    let (bx, by, bw, bh) = b;
-
-    ax < bx + bw as i32
-        && ax + aw as i32 > bx
-        && ay < by + bh as i32
-        && ay + ah as i32 > by
+pub fn rects_collide(a: Rect, b: Rect) -> bool {
+    a.intersects(&b)
 }
 
 // =============================================================================
@@ -501,47 +1198,125 @@ pub fn rects_collide(a: (i32, i32, u32, u32), b: (i32, i32, u32, u32)) -> bool {
 
 #[derive(Clone)]
 pub struct Game {
+    /// Tuning values; change through `Game::with_config`
+    pub config: GameConfig,
     pub state: GameState,
     pub otter: Otter,
-    pub missiles: Vec<Missile>,
-    pub coins: Vec<Coin>,
-    pub fish: Vec<Fish>,
+    /// Missiles, coins, fish, seabed hazards and power-up pickups, in the
+    /// order they spawned
+    pub entities: Vec<AnyEntity>,
     pub score: u32,
     pub high_score: u32,
     pub obstacle_timer: f32,
     pub obstacle_spawn_rate: f32,
-    /// Score threshold at which invincibility ends (None = not invincible)
-    pub invincibility_check_score: Option<u32>,
-    pending_sounds: Vec<SoundEffect>,
+    /// Power-ups in effect, including fish invincibility
+    pub power_ups: PowerUps,
+    /// Coins collected in the current combo (0 = no combo going)
+    pub coin_streak: u32,
+    /// Frames left to collect another coin before the combo ends
+    pub combo_timer: u32,
+    /// Frames left before play resumes after a pause (0 = running)
+    pub resume_countdown: u32,
+    pub stats: RunStats,
+    /// Top runs; load from and save to `SaveData` to keep it across sessions
+    pub leaderboard: Leaderboard,
+    /// Totals over all finished runs; kept across sessions like `leaderboard`
+    pub lifetime: LifetimeStats,
+    /// Initials being entered after a run that made the leaderboard
+    pub initials_entry: Option<InitialsEntry>,
+    /// Rank the last run reached on the leaderboard, if any
+    pub last_rank: Option<usize>,
+    /// Show the leaderboard instead of the menu / game over screen
+    pub show_leaderboard: bool,
+    /// Events since the last `take_events`
+    pending_events: Vec<GameEvent>,
+    /// Whether the current run has already passed the high score
+    high_score_beaten: bool,
+    /// Sprite masks for `GameConfig::precise_collision`
+    collision_masks: Option<Arc<CollisionMasks>>,
+    /// Whether the swim button is currently held down
+    space_held: bool,
+    /// Seed the RNG is reset to at the start of every run
+    seed: u64,
+    rng: StdRng,
 }
 
 impl Game {
+    /// Create a game with a random seed
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    /// Create a game whose runs are fully determined by `seed` and the input sequence
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_config(GameConfig::default(), seed)
+    }
+
+    /// Create a game with custom tuning
+    pub fn with_config(config: GameConfig, seed: u64) -> Self {
         Self {
             state: GameState::Menu,
-            otter: Otter::new(),
-            missiles: Vec::new(),
-            coins: Vec::new(),
-            fish: Vec::new(),
+            otter: Otter::from_config(&config),
+            entities: Vec::new(),
             score: 0,
             high_score: 0,
             obstacle_timer: 0.0,
-            obstacle_spawn_rate: 50.0,
-            invincibility_check_score: None,
-            pending_sounds: Vec::new(),
+            obstacle_spawn_rate: config.obstacle_spawn_rate_start,
+            power_ups: PowerUps::default(),
+            coin_streak: 0,
+            combo_timer: 0,
+            resume_countdown: 0,
+            stats: RunStats::default(),
+            leaderboard: Leaderboard::default(),
+            lifetime: LifetimeStats::default(),
+            initials_entry: None,
+            last_rank: None,
+            show_leaderboard: false,
+            pending_events: Vec::new(),
+            high_score_beaten: false,
+            collision_masks: None,
+            space_held: false,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            config,
         }
     }
 
+    /// The seed used for spawning in the current (or next) run
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Change the seed; takes effect immediately and at every subsequent reset
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Provide the sprite masks precise collision tests against
+    /// Without them, precise mode falls back to hitboxes only (replays
+    /// refuse to play back that way, see `Replay::new_game_with_config`)
+    pub fn set_collision_masks(&mut self, masks: Option<Arc<CollisionMasks>>) {
+        self.collision_masks = masks;
+    }
+
     pub fn reset(&mut self) {
-        self.otter.reset();
-        self.missiles.clear();
-        self.coins.clear();
-        self.fish.clear();
+        self.otter = Otter::from_config(&self.config);
+        self.entities.clear();
         self.score = 0;
         self.obstacle_timer = 0.0;
-        self.obstacle_spawn_rate = 50.0;
-        self.invincibility_check_score = None;
-        self.pending_sounds.clear();
+        self.obstacle_spawn_rate = self.config.obstacle_spawn_rate_start;
+        self.power_ups.clear();
+        self.coin_streak = 0;
+        self.combo_timer = 0;
+        self.resume_countdown = 0;
+        self.stats = RunStats::default();
+        self.initials_entry = None;
+        self.last_rank = None;
+        self.show_leaderboard = false;
+        self.pending_events.clear();
+        self.high_score_beaten = false;
+        self.rng = StdRng::seed_from_u64(self.seed);
         self.state = GameState::Playing;
     }
 
@@ -551,9 +1326,77 @@ impl Game {
         }
     }
 
-    /// Take any pending sound effects (renderer should play these)
+    pub fn missiles(&self) -> impl Iterator<Item = &Missile> {
+        self.entities.iter().filter_map(AnyEntity::as_missile)
+    }
+
+    pub fn coins(&self) -> impl Iterator<Item = &Coin> {
+        self.entities.iter().filter_map(AnyEntity::as_coin)
+    }
+
+    pub fn fish(&self) -> impl Iterator<Item = &Fish> {
+        self.entities.iter().filter_map(AnyEntity::as_fish)
+    }
+
+    /// Score threshold at which invincibility ends (None = not invincible)
+    pub fn invincibility_check_score(&self) -> Option<u32> {
+        match self.power_ups.expiry(PowerUp::Invincibility) {
+            Some(Expiry::AtScore(score)) => Some(score),
+            _ => None,
+        }
+    }
+
+    /// Start `power_up`, or restart it if it's already running
+    pub fn start_power_up(&mut self, power_up: PowerUp) {
+        let expiry = match power_up {
+            // Lasts until the score increases by invincibility_score_duration
+            PowerUp::Invincibility => {
+                self.otter.activate_invincibility();
+                Expiry::AtScore(self.score + self.config.invincibility_score_duration)
+            }
+            PowerUp::Magnet => Expiry::AfterFrames(self.config.magnet_frames),
+            PowerUp::Shield => Expiry::AfterFrames(self.config.shield_frames),
+            PowerUp::SlowMotion => Expiry::AfterFrames(self.config.slow_motion_frames),
+        };
+        self.power_ups.activate(power_up, expiry);
+        self.pending_events.push(GameEvent::PowerUpStarted(power_up));
+    }
+
+    /// Stop `power_up` early; returns whether it was running
+    pub fn end_power_up(&mut self, power_up: PowerUp) -> bool {
+        let was_active = self.power_ups.deactivate(power_up);
+        if was_active {
+            self.power_up_ended(power_up);
+        }
+        was_active
+    }
+
+    fn power_up_ended(&mut self, power_up: PowerUp) {
+        if power_up == PowerUp::Invincibility {
+            self.otter.deactivate_invincibility();
+        }
+        self.pending_events.push(GameEvent::PowerUpEnded(power_up));
+    }
+
+    /// What the next coin's score is multiplied by
+    pub fn combo_multiplier(&self) -> u32 {
+        self.coin_streak.clamp(1, self.config.combo_max_multiplier.max(1))
+    }
+
+    fn break_combo(&mut self) {
+        self.coin_streak = 0;
+        self.combo_timer = 0;
+    }
+
+    /// Take the events queued since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// Take the pending events' sound effects, dropping the events themselves
+    #[deprecated(note = "drains the event queue; use `take_events` and `GameEvent::sound`")]
     pub fn take_pending_sounds(&mut self) -> Vec<SoundEffect> {
-        std::mem::take(&mut self.pending_sounds)
+        self.take_events().iter().filter_map(GameEvent::sound).collect()
     }
 
     /// Get the digits of the score for rendering
@@ -591,139 +1434,362 @@ impl Game {
     }
 
     pub fn handle_space_pressed(&mut self) {
+        self.space_held = true;
         match self.state {
+            // One-button initials entry: tap to cycle the letter, hold to confirm
+            GameState::GameOver if self.is_entering_initials() => {
+                if let Some(entry) = &mut self.initials_entry {
+                    entry.press();
+                }
+            }
             GameState::Menu | GameState::GameOver => {
                 self.start();
             }
             GameState::Playing => {
                 self.otter.swim_up();
             }
+            // Held state is remembered and applied once play resumes
+            GameState::Paused => {}
         }
     }
 
     pub fn handle_space_released(&mut self) {
-        if self.state == GameState::Playing {
+        self.space_held = false;
+        if let Some(input) = self.initials_entry.as_mut().and_then(InitialsEntry::release) {
+            self.handle_initials_input(input);
+        }
+        // Also while paused, so the otter doesn't resume stuck swimming up
+        if self.state == GameState::Playing || self.state == GameState::Paused {
             self.otter.swim_down();
         }
     }
 
+    /// Freeze a run in progress
+    pub fn pause(&mut self) {
+        if self.state == GameState::Playing {
+            self.state = GameState::Paused;
+            self.resume_countdown = 0;
+            // Nothing moves until play restarts, so stop interpolating
+            self.hold_positions();
+        }
+    }
+
+    /// Unfreeze a paused run; play restarts after `RESUME_COUNTDOWN_FRAMES`
+    pub fn resume(&mut self) {
+        if self.state == GameState::Paused {
+            self.state = GameState::Playing;
+            self.resume_countdown = RESUME_COUNTDOWN_FRAMES;
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        match self.state {
+            GameState::Playing => self.pause(),
+            GameState::Paused => self.resume(),
+            _ => {}
+        }
+    }
+
     pub fn update(&mut self) {
+        self.tick_initials_hold();
         if self.state != GameState::Playing {
             return;
         }
 
+        // Hold everything still until the resume countdown runs out
+        if self.resume_countdown > 0 {
+            self.resume_countdown -= 1;
+            return;
+        }
+
+        // Keep swimming up while space is held (e.g. after invincibility wears off)
+        if self.space_held {
+            self.otter.swim_up();
+        }
+
         // Add per-frame score (from original main.cpp: score += 7)
-        self.score += SCORE_PER_FRAME;
-
-        // Check if invincibility should end (score-based, from original)
-        if self.otter.is_invincible {
-            if let Some(check_score) = self.invincibility_check_score {
-                if self.score >= check_score {
-                    self.otter.deactivate_invincibility();
-                    self.invincibility_check_score = None;
-                }
+        self.score += self.config.score_per_frame;
+        self.stats.frames += 1;
+
+        // A combo ends if no coin follows within the window
+        if self.combo_timer > 0 {
+            self.combo_timer -= 1;
+            if self.combo_timer == 0 {
+                self.break_combo();
             }
         }
 
+        // End power-ups that have run out (invincibility is score-based, from original)
+        for power_up in self.power_ups.tick(self.score) {
+            self.power_up_ended(power_up);
+        }
+
         // Update otter
+        let was_walking = self.otter.state == OtterState::Walking;
         self.otter.update();
-
-        // Update missiles
-        for missile in &mut self.missiles {
-            missile.update();
+        if self.otter.state == OtterState::Walking {
+            if !was_walking {
+                self.pending_events.push(GameEvent::OtterLanded);
+            }
+            self.stats.walking_frames += 1;
         }
-        self.missiles.retain(|m| m.active);
 
-        // Update coins
-        for coin in &mut self.coins {
-            coin.update();
+        // Update missiles, coins, fish, seabed hazards and pickups
+        let slowed = self.power_ups.is_active(PowerUp::SlowMotion);
+        for entity in &mut self.entities {
+            entity.set_slowed(slowed);
+            entity.aim(&self.otter);
+            entity.update();
         }
-        self.coins.retain(|c| c.active && !c.collected);
-
-        // Update fish
-        for fish in &mut self.fish {
-            fish.update();
+        if self.power_ups.is_active(PowerUp::Magnet) {
+            self.pull_coins();
         }
-        self.fish.retain(|f| f.active);
+        // Letting a coin sink also ends a combo
+        if self.coins().any(Coin::is_missed) {
+            self.break_combo();
+        }
+        self.count_departures();
+        self.entities.retain(|e| e.is_active());
 
         // Spawn entities
         self.spawn_entities();
 
         // Check collisions
         self.check_collisions();
+        self.check_high_score();
 
         // Increase difficulty based on score
-        self.obstacle_spawn_rate = (50.0 - (self.score as f32 / 100.0)).max(10.0);
+        self.obstacle_spawn_rate = self.config.obstacle_spawn_rate(self.score);
     }
 
     fn spawn_entities(&mut self) {
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         // Spawn missiles (dynamic rate)
         self.obstacle_timer += 1.0;
         if self.obstacle_timer > self.obstacle_spawn_rate {
             self.obstacle_timer = 0.0;
-            let y = rng.gen_range(30..280);
-            self.missiles.push(Missile::new(y));
+            let y = rng.gen_range(MISSILE_SPAWN_MIN_Y..MISSILE_SPAWN_MAX_Y);
+            // Only roll for a kind once there is a choice, so runs play out
+            // exactly as before until the first new kind unlocks
+            let kinds = self.config.missile_kinds(self.score);
+            let kind = match kinds.len() {
+                1 => kinds[0],
+                n => kinds[rng.gen_range(0..n)],
+            };
+            self.entities
+                .push(Missile::of_kind(kind, y, &self.config).into());
         }
 
-        // Spawn coins (5% chance per frame)
-        if rng.gen_range(0..1000) < COIN_SPAWN_CHANCE {
+        // Spawn coins (5% chance per frame by default)
+        if rng.gen_range(0..1000) < self.config.coin_spawn_chance {
             let x = rng.gen_range(50..400);
-            self.coins.push(Coin::new(x));
+            self.entities.push(
+                Coin::with_velocity(
+                    x,
+                    self.config.coin_velocity_y,
+                    self.config.coin_velocity_x_water,
+                )
+                .into(),
+            );
         }
 
-        // Spawn fish (0.3% chance - rare)
-        if rng.gen_range(0..1000) < FISH_SPAWN_CHANCE {
+        // Spawn fish (0.3% chance by default - rare)
+        if rng.gen_range(0..1000) < self.config.fish_spawn_chance {
             let y = rng.gen_range(50..250);
-            self.fish.push(Fish::new(y));
+            self.entities
+                .push(Fish::with_velocity(y, self.config.fish_velocity_x).into());
+        }
+
+        // Spawn seabed hazards, so walking along the bottom isn't a free ride
+        if rng.gen_range(0..1000) < self.config.rock_spawn_chance {
+            self.entities
+                .push(Rock::with_velocity(self.config.seabed_velocity_x).into());
+        }
+        if rng.gen_range(0..1000) < self.config.crab_spawn_chance {
+            self.entities
+                .push(Crab::with_velocity(self.config.crab_velocity_x).into());
+        }
+
+        // Spawn power-up pickups (0.4% chance by default)
+        if rng.gen_range(0..1000) < self.config.power_up_spawn_chance {
+            let y = rng.gen_range(50..250);
+            let power_up = PowerUp::PICKUPS[rng.gen_range(0..PowerUp::PICKUPS.len())];
+            self.entities.push(
+                PowerUpPickup::new(power_up, y, self.config.power_up_velocity_x).into(),
+            );
+        }
+    }
+
+    /// Count coins and missiles about to leave play without touching the otter
+    fn count_departures(&mut self) {
+        let coins_missed = self
+            .coins()
+            .filter(|coin| !coin.active && !coin.collected)
+            .count();
+        let missiles_dodged = self
+            .missiles()
+            .filter(|missile| !missile.active && missile.state == MissileState::Shooting)
+            .count();
+        self.stats.coins_missed += coins_missed as u32;
+        self.stats.missiles_dodged += missiles_dodged as u32;
+    }
+
+    /// Draw coins near the otter towards it
+    fn pull_coins(&mut self) {
+        let (otter_x, otter_y) = self.otter.get_collision_rect().center();
+        for coin in self.entities.iter_mut().filter_map(AnyEntity::as_coin_mut) {
+            let (coin_x, coin_y) = coin.get_collision_rect().center();
+            let (dx, dy) = (otter_x - coin_x, otter_y - coin_y);
+            if dx * dx + dy * dy <= MAGNET_RADIUS * MAGNET_RADIUS {
+                coin.motion.x += dx.clamp(-MAGNET_PULL, MAGNET_PULL);
+                coin.motion.y += dy.clamp(-MAGNET_PULL, MAGNET_PULL);
+            }
+        }
+    }
+
+    /// Announce the first time in a run that the score passes a previous high score
+    fn check_high_score(&mut self) {
+        if !self.high_score_beaten && self.high_score > 0 && self.score > self.high_score {
+            self.high_score_beaten = true;
+            self.pending_events.push(GameEvent::HighScoreBeaten {
+                previous: self.high_score,
+            });
         }
     }
 
+    fn end_run(&mut self, cause: DeathCause) {
+        self.check_high_score();
+        self.stats.cause_of_death = Some(cause);
+        self.lifetime.add_run(&self.stats);
+        self.pending_events.push(GameEvent::GameOver {
+            score: self.score,
+            cause,
+        });
+        if self.score > self.high_score {
+            self.high_score = self.score;
+        }
+        if self.leaderboard.qualifies(self.score) {
+            self.initials_entry = Some(InitialsEntry::new());
+        }
+        self.state = GameState::GameOver;
+    }
+
+    /// Make the previous positions match the current ones
+    fn hold_positions(&mut self) {
+        self.otter.hold_position();
+        self.entities.iter_mut().for_each(AnyEntity::hold_position);
+    }
+
     fn check_collisions(&mut self) {
         let otter_rect = self.otter.get_collision_rect();
 
-        // Check missile collisions
-        for missile in &mut self.missiles {
-            if missile.state == MissileState::Shooting
-                && rects_collide(otter_rect, missile.get_collision_rect())
-            {
-                if self.otter.is_invincible {
+        // Hitboxes first; in precise mode the sprites must also touch
+        let masks = self
+            .collision_masks
+            .clone()
+            .filter(|_| self.config.precise_collision);
+        let otter_sprite = Sprite::new(
+            TextureId::Otter,
+            self.otter.get_clip(),
+            self.otter.motion.x,
+            self.otter.motion.y,
+        );
+        let touches = |rect, sprite| {
+            rects_collide(otter_rect, rect)
+                && masks
+                    .as_ref()
+                    .is_none_or(|masks| masks.sprites_overlap(otter_sprite, sprite))
+        };
+
+        // Find everything the otter touches, then resolve in `Contact` order
+        let mut contacts: Vec<(usize, Contact)> = self
+            .entities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entity)| {
+                let contact = entity.contact(&self.otter)?;
+                touches(entity.get_collision_rect(), entity.sprite()).then_some((i, contact))
+            })
+            .collect();
+        contacts.sort_by_key(|&(_, contact)| contact);
+
+        // A shield absorbs everything that hits in the frame it breaks
+        let mut shield_broke = false;
+        for (i, contact) in contacts {
+            self.entities[i].on_contact();
+            let (x, y) = self.entities[i].get_collision_rect().center();
+            match contact {
+                Contact::Fatal => {
+                    let cause = DeathCause::of(&self.entities[i])
+                        .expect("only hazards make fatal contact");
+                    if shield_broke || self.end_power_up(PowerUp::Shield) {
+                        shield_broke = true;
+                        self.pending_events.push(GameEvent::HitAbsorbed { cause });
+                    } else {
+                        self.end_run(cause);
+                        return;
+                    }
+                }
+                Contact::Destroyed { score } => {
                     // Destroy missile when invincible and get bonus score
-                    missile.explode();
-                    self.score += MISSILE_DESTROY_SCORE;
-                    self.pending_sounds.push(SoundEffect::Boom);
-                } else {
-                    // Game over
-                    missile.explode();
-                    self.pending_sounds.push(SoundEffect::Boom);
-                    if self.score > self.high_score {
-                        self.high_score = self.score;
+                    self.score += score;
+                    self.stats.missiles_destroyed += 1;
+                    self.pending_events
+                        .push(GameEvent::MissileDestroyed { x, y, score });
+                }
+                Contact::Coin => {
+                    // Each coin in a combo is worth more, up to combo_max_multiplier
+                    self.coin_streak += 1;
+                    self.combo_timer = self.config.combo_window_frames;
+                    self.stats.best_streak = self.stats.best_streak.max(self.coin_streak);
+                    let value = self.config.coin_score * self.combo_multiplier();
+                    self.score += value;
+                    self.stats.coins_collected += 1;
+                    self.pending_events
+                        .push(GameEvent::CoinCollected { x, y, value });
+                }
+                Contact::PowerUp(power_up) => {
+                    if power_up == PowerUp::Invincibility {
+                        self.stats.fish_eaten += 1;
                     }
-                    self.state = GameState::GameOver;
-                    return;
+                    self.start_power_up(power_up);
                 }
             }
         }
 
-        // Check coin collisions
-        for coin in &mut self.coins {
-            if !coin.collected && rects_collide(otter_rect, coin.get_collision_rect()) {
-                coin.collected = true;
-                self.score += COIN_SCORE;
-                self.pending_sounds.push(SoundEffect::Coin);
+        self.check_near_misses(otter_rect);
+    }
+
+    /// Score missiles that flew past within `near_miss_margin` of the otter
+    /// without touching it, once each
+    fn check_near_misses(&mut self, otter_rect: Rect) {
+        let graze_rect = otter_rect.expand(self.config.near_miss_margin);
+        let mut near_misses = 0;
+        for missile in self.entities.iter_mut().filter_map(AnyEntity::as_missile_mut) {
+            if missile.near_miss_scored {
+                continue;
+            }
+            let rect = missile.get_collision_rect();
+            if rects_collide(graze_rect, rect) {
+                // Only while it could still end the run, and not touching
+                if missile.contact(&self.otter) == Some(Contact::Fatal)
+                    && !rects_collide(otter_rect, rect)
+                {
+                    missile.grazed = true;
+                }
+            } else if missile.grazed && missile.state == MissileState::Shooting {
+                missile.near_miss_scored = true;
+                near_misses += 1;
             }
         }
 
-        // Check fish collisions (activates score-based invincibility)
-        for fish in &mut self.fish {
-            if fish.active && rects_collide(otter_rect, fish.get_collision_rect()) {
-                fish.active = false;
-                self.otter.activate_invincibility();
-                // Invincibility lasts until score increases by INVINCIBILITY_SCORE_DURATION
-                self.invincibility_check_score = Some(self.score + INVINCIBILITY_SCORE_DURATION);
-                self.pending_sounds.push(SoundEffect::Powerup);
-            }
+        for _ in 0..near_misses {
+            self.score += self.config.near_miss_score;
+            self.stats.near_misses += 1;
+            self.pending_events.push(GameEvent::NearMiss {
+                score: self.config.near_miss_score,
+            });
         }
     }
 }
@@ -745,8 +1811,8 @@ mod tests {
     #[test]
     fn test_otter_initialization() {
         let otter = Otter::new();
-        assert_eq!(otter.x, OTTER_START_X);
-        assert_eq!(otter.y, OTTER_START_Y);
+        assert_eq!(otter.motion.x, OTTER_START_X);
+        assert_eq!(otter.motion.y, OTTER_START_Y);
         assert_eq!(otter.state, OtterState::SwimmingDown);
         assert!(!otter.is_invincible);
     }
@@ -813,11 +1879,10 @@ mod tests {
         game.score = 1000;
 
         // Simulate eating a fish
-        game.otter.activate_invincibility();
-        game.invincibility_check_score = Some(game.score + INVINCIBILITY_SCORE_DURATION);
+        game.start_power_up(PowerUp::Invincibility);
 
         assert!(game.otter.is_invincible);
-        assert_eq!(game.invincibility_check_score, Some(1000 + INVINCIBILITY_SCORE_DURATION));
+        assert_eq!(game.invincibility_check_score(), Some(1000 + INVINCIBILITY_SCORE_DURATION));
 
         // Set score to well before threshold (accounting for SCORE_PER_FRAME being added each update)
         // Threshold is 3000 (1000 + 2000), so set to 2990 - after update it becomes 2997, still < 3000
@@ -835,27 +1900,88 @@ mod tests {
     #[test]
     fn test_otter_boundary_top() {
         let mut otter = Otter::new();
-        otter.y = 0;
+        otter.motion.y = 0;
         otter.velocity_y = -OTTER_VELOCITY;
         otter.update();
-        assert!(otter.y >= OTTER_MIN_Y);
+        assert!(otter.motion.y >= OTTER_MIN_Y);
     }
 
     #[test]
     fn test_otter_boundary_bottom() {
         let mut otter = Otter::new();
-        otter.y = SCREEN_HEIGHT;
+        otter.motion.y = SCREEN_HEIGHT;
         otter.velocity_y = OTTER_VELOCITY;
         otter.update();
-        assert!(otter.y + OTTER_HEIGHT <= SCREEN_HEIGHT);
+        assert!(otter.motion.y + OTTER_HEIGHT <= SCREEN_HEIGHT);
+    }
+
+    fn smooth_otter() -> Otter {
+        let config = GameConfig {
+            otter_physics: OtterPhysics::Smooth,
+            ..GameConfig::default()
+        };
+        Otter::from_config(&config)
+    }
+
+    #[test]
+    fn test_smooth_otter_accelerates_to_terminal_velocity() {
+        let mut otter = smooth_otter();
+        otter.motion.y = 100;
+        otter.update();
+        assert_eq!(otter.sub_velocity_y, OTTER_SINK_ACCELERATION);
+        assert_eq!(otter.motion.y, 104);
+
+        for _ in 0..10 {
+            otter.update();
+        }
+        assert_eq!(otter.sub_velocity_y, OTTER_TERMINAL_VELOCITY);
+
+        // Thrusting slows the fall before the otter starts rising
+        otter.motion.y = 100;
+        otter.swim_up();
+        assert_eq!(otter.state, OtterState::SwimmingUp);
+        otter.update();
+        assert!(otter.velocity_y > 0);
+        for _ in 0..5 {
+            otter.update();
+        }
+        assert!(otter.velocity_y < 0);
+        assert!(otter.sub_velocity_y >= -OTTER_TERMINAL_VELOCITY);
+    }
+
+    #[test]
+    fn test_smooth_otter_keeps_sub_pixel_position() {
+        let mut otter = smooth_otter();
+        otter.motion.y = 100;
+        otter.sink_acceleration = SUBPIXELS_PER_PIXEL / 2;
+        otter.update();
+        assert_eq!(otter.motion.y, 100);
+        otter.update();
+        assert_eq!(otter.motion.y, 101);
+        assert_eq!(otter.sub_y, 101 * SUBPIXELS_PER_PIXEL + SUBPIXELS_PER_PIXEL / 2);
+    }
+
+    #[test]
+    fn test_smooth_otter_stops_on_seabed() {
+        let mut otter = smooth_otter();
+        otter.motion.y = OTTER_WALK_Y - 1;
+        otter.sub_velocity_y = OTTER_TERMINAL_VELOCITY;
+        otter.update();
+        assert_eq!(otter.motion.y, OTTER_WALK_Y);
+        assert_eq!(otter.state, OtterState::Walking);
+        assert_eq!(otter.sub_velocity_y, 0);
+
+        // Reset keeps the movement model
+        otter.reset();
+        assert_eq!(otter.physics, OtterPhysics::Smooth);
     }
 
     #[test]
     fn test_missile_movement() {
         let mut missile = Missile::new(100);
-        let initial_x = missile.x;
+        let initial_x = missile.motion.x;
         missile.update();
-        assert_eq!(missile.x, initial_x - MISSILE_VELOCITY_X);
+        assert_eq!(missile.motion.x, initial_x - MISSILE_VELOCITY_X);
     }
 
     #[test]
@@ -869,23 +1995,99 @@ mod tests {
     #[test]
     fn test_missile_deactivates_offscreen() {
         let mut missile = Missile::new(100);
-        missile.x = -60;
+        missile.motion.x = -60;
         missile.update();
         assert!(!missile.active);
     }
 
+    #[test]
+    fn test_homing_missile_drifts_towards_otter() {
+        let config = GameConfig::default();
+        let mut missile = Missile::of_kind(MissileKind::Homing, 100, &config);
+        let mut otter = Otter::new();
+        otter.motion.y = 200;
+        missile.aim(&otter);
+        missile.update();
+        assert_eq!(missile.motion.y, 100 + HOMING_MISSILE_TURN);
+        assert_eq!(missile.destroy_score, HOMING_MISSILE_DESTROY_SCORE);
+
+        // Once level, it stays level
+        for _ in 0..100 {
+            missile.aim(&otter);
+            missile.update();
+        }
+        let target = otter.get_collision_rect().center().1;
+        assert_eq!(missile.get_collision_rect().center().1, target);
+    }
+
+    #[test]
+    fn test_homing_missile_turn_is_configurable() {
+        let config = GameConfig {
+            homing_missile_turn: 7,
+            ..GameConfig::default()
+        };
+        let mut missile = Missile::of_kind(MissileKind::Homing, 100, &config);
+        let mut otter = Otter::new();
+        otter.motion.y = 200;
+        missile.aim(&otter);
+        missile.update();
+        assert_eq!(missile.motion.y, 107);
+    }
+
+    #[test]
+    fn test_wave_missile_weaves_inside_spawn_band() {
+        let config = GameConfig::default();
+        let mut missile = Missile::of_kind(MissileKind::Wave, 30, &config);
+        let base_y = missile.motion.y;
+        let mut heights = Vec::new();
+        for _ in 0..WAVE_MISSILE_OFFSETS.len() {
+            missile.update();
+            heights.push(missile.motion.y);
+        }
+        assert_eq!(heights.iter().max(), Some(&(base_y + WAVE_MISSILE_AMPLITUDE)));
+        assert_eq!(heights.iter().min(), Some(&(base_y - WAVE_MISSILE_AMPLITUDE)));
+        assert!(heights
+            .iter()
+            .all(|&y| (MISSILE_SPAWN_MIN_Y..MISSILE_SPAWN_MAX_Y).contains(&y)));
+    }
+
+    #[test]
+    fn test_fast_missile_warns_before_launching() {
+        let config = GameConfig::default();
+        let otter = Otter::new();
+        let mut missile = Missile::of_kind(MissileKind::Fast, 100, &config);
+        assert!(missile.is_warning());
+        assert_eq!(missile.texture(), TextureId::Font);
+        assert_eq!(Some(missile.get_clip()), glyph_clip('!'));
+        assert_eq!(missile.contact(&otter), None);
+
+        for _ in 0..FAST_MISSILE_WARNING_FRAMES {
+            missile.update();
+        }
+        assert!(!missile.is_warning());
+        assert_eq!(
+            (missile.motion.x, missile.motion.prev_x),
+            (SCREEN_WIDTH, SCREEN_WIDTH)
+        );
+        assert_eq!(missile.contact(&otter), Some(Contact::Fatal));
+
+        missile.update();
+        assert_eq!(missile.motion.x, SCREEN_WIDTH - FAST_MISSILE_VELOCITY_X);
+        assert_eq!(missile.get_collision_rect().w, 32);
+    }
+
     #[test]
     fn test_coin_movement() {
         let mut coin = Coin::new(200);
-        let initial_y = coin.y;
+        let initial_y = coin.motion.y;
         coin.update();
-        assert_eq!(coin.y, initial_y + COIN_VELOCITY_Y);
+        assert_eq!(coin.motion.y, initial_y + COIN_VELOCITY_Y);
     }
 
     #[test]
     fn test_coin_water_drift() {
         let mut coin = Coin::new(200);
-        coin.y = COIN_WATER_THRESHOLD + 1;
+        coin.motion.y = COIN_WATER_THRESHOLD + 1;
         coin.update();
         assert_eq!(coin.velocity_x, COIN_VELOCITY_X_WATER);
     }
@@ -893,21 +2095,43 @@ mod tests {
     #[test]
     fn test_fish_movement() {
         let mut fish = Fish::new(100);
-        let initial_x = fish.x;
+        let initial_x = fish.motion.x;
         fish.update();
-        assert_eq!(fish.x, initial_x - FISH_VELOCITY_X);
+        assert_eq!(fish.motion.x, initial_x - FISH_VELOCITY_X);
+    }
+
+    #[test]
+    fn test_seabed_hazards_rest_on_seabed() {
+        let mut rock = Rock::new();
+        let mut crab = Crab::new();
+        assert_eq!(rock.motion.y + rock.get_clip().h as i32, SEABED_Y);
+        assert_eq!(crab.motion.y + crab.get_clip().h as i32, SEABED_Y);
+
+        rock.update();
+        crab.update();
+        assert_eq!(rock.motion.x, SCREEN_WIDTH - SEABED_VELOCITY_X);
+        assert_eq!(crab.motion.x, SCREEN_WIDTH - CRAB_VELOCITY_X);
+        assert_eq!(crab.frame, 1);
+
+        // Only an otter down by the floor is in reach
+        let mut otter = Otter::new();
+        otter.motion.x = rock.motion.x;
+        otter.motion.y = 200;
+        assert!(!rects_collide(otter.get_collision_rect(), rock.get_collision_rect()));
+        otter.motion.y = OTTER_WALK_Y;
+        assert!(rects_collide(otter.get_collision_rect(), rock.get_collision_rect()));
     }
 
     #[test]
     fn test_collision_detection() {
         // Overlapping rects
-        assert!(rects_collide((0, 0, 10, 10), (5, 5, 10, 10)));
+        assert!(rects_collide(Rect::new(0, 0, 10, 10), Rect::new(5, 5, 10, 10)));
 
         // Non-overlapping rects
-        assert!(!rects_collide((0, 0, 10, 10), (20, 20, 10, 10)));
+        assert!(!rects_collide(Rect::new(0, 0, 10, 10), Rect::new(20, 20, 10, 10)));
 
         // Edge touching (not overlapping)
-        assert!(!rects_collide((0, 0, 10, 10), (10, 0, 10, 10)));
+        assert!(!rects_collide(Rect::new(0, 0, 10, 10), Rect::new(10, 0, 10, 10)));
     }
 
     #[test]
@@ -915,9 +2139,7 @@ mod tests {
         let game = Game::new();
         assert_eq!(game.state, GameState::Menu);
         assert_eq!(game.score, 0);
-        assert!(game.missiles.is_empty());
-        assert!(game.coins.is_empty());
-        assert!(game.fish.is_empty());
+        assert!(game.entities.is_empty());
     }
 
     #[test]
@@ -931,11 +2153,80 @@ mod tests {
     fn test_game_reset() {
         let mut game = Game::new();
         game.score = 1000;
-        game.missiles.push(Missile::new(100));
+        game.entities.push(Missile::new(100).into());
         game.reset();
         assert_eq!(game.score, 0);
-        assert!(game.missiles.is_empty());
+        assert!(game.entities.is_empty());
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_stats_count_frames_and_reset() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        for _ in 0..5 {
+            game.update();
+        }
+        assert_eq!(game.stats.frames, 5);
+
+        game.reset();
+        assert_eq!(game.stats, RunStats::default());
+    }
+
+    #[test]
+    fn test_reset_reseeds_rng() {
+        let mut game = Game::with_seed(42);
+        game.start();
+        let first: u32 = game.rng.gen();
+        game.reset();
+        let second: u32 = game.rng.gen();
+        assert_eq!(first, second);
+        assert_eq!(game.seed(), 42);
+    }
+
+    #[test]
+    fn test_pause_freezes_update() {
+        let mut game = Game::with_seed(1);
+        game.start();
+        game.update();
+        let score = game.score;
+
+        game.pause();
+        assert_eq!(game.state, GameState::Paused);
+        let otter = game.otter.clone();
+        for _ in 0..10 {
+            game.update();
+        }
+        assert_eq!(game.score, score);
+        assert_eq!(game.otter, otter);
+    }
+
+    #[test]
+    fn test_resume_countdown() {
+        let mut game = Game::with_seed(1);
+        game.start();
+        game.toggle_pause();
+        game.toggle_pause();
         assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.resume_countdown, RESUME_COUNTDOWN_FRAMES);
+
+        for _ in 0..RESUME_COUNTDOWN_FRAMES {
+            game.update();
+        }
+        assert_eq!(game.score, 0, "Nothing moves during the countdown");
+
+        game.update();
+        assert_eq!(game.score, SCORE_PER_FRAME);
+    }
+
+    #[test]
+    fn test_release_while_paused_swims_down() {
+        let mut game = Game::with_seed(1);
+        game.start();
+        game.handle_space_pressed();
+        game.pause();
+        game.handle_space_released();
+        assert_eq!(game.otter.velocity_y, OTTER_VELOCITY);
     }
 
     #[test]
@@ -958,6 +2249,18 @@ mod tests {
         assert!(!FISH_CLIPS_SWIM.is_empty());
         assert!(!NUMBER_CLIPS.is_empty());
         assert_eq!(NUMBER_CLIPS.len(), 10); // 0-9 digits
+        assert_eq!(LETTER_CLIPS.len(), 26);
+        assert_eq!(FONT_DIGIT_CLIPS.len(), 10);
+        assert_eq!(FONT_SYMBOL_CLIPS.len(), FONT_SYMBOLS.len());
+    }
+
+    #[test]
+    fn test_glyph_clip() {
+        assert_eq!(glyph_clip('A'), Some(LETTER_CLIPS[0]));
+        assert_eq!(glyph_clip('z'), Some(LETTER_CLIPS[25]));
+        assert_eq!(glyph_clip('7'), Some(FONT_DIGIT_CLIPS[7]));
+        assert_eq!(glyph_clip(':'), Some(FONT_SYMBOL_CLIPS[2]));
+        assert_eq!(glyph_clip(' '), None);
     }
 
     #[test]
@@ -0,0 +1,322 @@
+//! Pixel-perfect collision masks
+//!
+//! With `GameConfig::precise_collision` set, two entities only collide if
+//! their hitboxes overlap (the cheap `rects_collide` test) *and* their
+//! sprites share an opaque pixel. The masks are built once from the
+//! sprite sheets, one per `ClipRect` a sprite can show, and handed to the
+//! game with `Game::set_collision_masks`. A pixel is transparent if it is
+//! the magenta color key or (in 32-bit sheets) mostly transparent alpha.
+
+use crate::render::TextureId;
+use crate::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Transparent color in every sprite sheet
+pub const COLOR_KEY: [u8; 3] = [255, 0, 255];
+
+/// Pixels with less alpha than this are transparent
+pub const ALPHA_THRESHOLD: u8 = 128;
+
+/// A decoded RGBA image, top row first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// `pixels` must hold `width * height` RGBA values, row by row from the top
+    pub fn new(width: u32, height: u32, pixels: Vec<[u8; 4]>) -> Self {
+        assert_eq!(pixels.len(), (width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn load_bmp(path: &Path) -> io::Result<Self> {
+        Self::from_bmp(&fs::read(path)?)
+    }
+
+    /// Decode an uncompressed 24- or 32-bit Windows bitmap
+    ///
+    /// Like SDL, a 32-bit bitmap whose alpha bytes are all zero is opaque.
+    pub fn from_bmp(bytes: &[u8]) -> io::Result<Self> {
+        let u16_at = |at: usize| {
+            bytes
+                .get(at..at + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        };
+        let u32_at = |at: usize| {
+            bytes
+                .get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        };
+        let header = || invalid_data("truncated bitmap header");
+
+        if bytes.get(0..2) != Some(b"BM") {
+            return Err(invalid_data("not a bitmap"));
+        }
+        let data_offset = u32_at(10).ok_or_else(header)? as usize;
+        let width = u32_at(18).ok_or_else(header)? as i32;
+        let height = u32_at(22).ok_or_else(header)? as i32;
+        let bits = u16_at(28).ok_or_else(header)?;
+        let compression = u32_at(30).ok_or_else(header)?;
+
+        // 32-bit sheets may declare BI_BITFIELDS with the standard BGRA layout
+        if bits != 24 && bits != 32 || compression != 0 && !(bits == 32 && compression == 3) {
+            return Err(invalid_data(
+                "only uncompressed 24/32-bit bitmaps are supported",
+            ));
+        }
+        if width <= 0 || height == 0 {
+            return Err(invalid_data("empty bitmap"));
+        }
+
+        // Rows are stored bottom-up unless the height is negative
+        let (width, rows) = (width as u32, height.unsigned_abs());
+        let bytes_per_pixel = bits as usize / 8;
+        let stride = (width as usize * bytes_per_pixel).div_ceil(4) * 4;
+        let mut pixels = Vec::with_capacity((width * rows) as usize);
+        for row in 0..rows as usize {
+            let stored_row = if height > 0 {
+                rows as usize - 1 - row
+            } else {
+                row
+            };
+            let start = data_offset + stored_row * stride;
+            let line = bytes
+                .get(start..start + width as usize * bytes_per_pixel)
+                .ok_or_else(|| invalid_data("truncated bitmap pixels"))?;
+            pixels.extend(line.chunks_exact(bytes_per_pixel).map(|bgra| {
+                let alpha = if bits == 32 { bgra[3] } else { u8::MAX };
+                [bgra[2], bgra[1], bgra[0], alpha]
+            }));
+        }
+
+        if bits == 32 && pixels.iter().all(|p| p[3] == 0) {
+            pixels.iter_mut().for_each(|p| p[3] = u8::MAX);
+        }
+        Ok(Self::new(width, rows, pixels))
+    }
+
+    /// RGBA value at (x, y), or None outside the image
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(self.pixels[(y as u32 * self.width + x as u32) as usize])
+    }
+}
+
+/// Opaque pixels of one sprite clip
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    pub width: u32,
+    pub height: u32,
+    solid: Vec<bool>,
+}
+
+impl Mask {
+    /// Mask of `clip` in a sprite sheet; anything outside the sheet is transparent
+    pub fn from_image(image: &Image, clip: ClipRect) -> Self {
+        let mut solid = Vec::with_capacity((clip.w * clip.h) as usize);
+        for y in 0..clip.h as i32 {
+            for x in 0..clip.w as i32 {
+                let pixel = image.pixel(clip.x + x, clip.y + y);
+                solid.push(pixel.is_some_and(|p| p[3] >= ALPHA_THRESHOLD && p[..3] != COLOR_KEY));
+            }
+        }
+        Self {
+            width: clip.w,
+            height: clip.h,
+            solid,
+        }
+    }
+
+    /// Whether the pixel at (x, y), relative to the clip's top-left, is opaque
+    pub fn is_solid(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && x < self.width as i32
+            && y < self.height as i32
+            && self.solid[(y as u32 * self.width + x as u32) as usize]
+    }
+
+    /// Whether this mask drawn at `at` shares an opaque pixel with `other` drawn at `other_at`
+    pub fn overlaps(&self, at: (i32, i32), other: &Mask, other_at: (i32, i32)) -> bool {
+        let bounds = Rect::new(at.0, at.1, self.width, self.height);
+        let other_bounds = Rect::new(other_at.0, other_at.1, other.width, other.height);
+        let Some(shared) = bounds.intersection(&other_bounds) else {
+            return false;
+        };
+
+        (shared.top()..shared.bottom()).any(|y| {
+            (shared.left()..shared.right()).any(|x| {
+                self.is_solid(x - at.0, y - at.1) && other.is_solid(x - other_at.0, y - other_at.1)
+            })
+        })
+    }
+}
+
+/// A sprite as drawn this frame: which clip of which sheet, and where
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    pub texture: TextureId,
+    pub clip: ClipRect,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Sprite {
+    pub fn new(texture: TextureId, clip: ClipRect, x: i32, y: i32) -> Self {
+        Self {
+            texture,
+            clip,
+            x,
+            y,
+        }
+    }
+}
+
+/// Masks for every clip that can take part in a collision
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CollisionMasks {
+    masks: HashMap<(TextureId, ClipRect), Mask>,
+}
+
+impl CollisionMasks {
+    /// Build masks from the sprite sheets in `assets/`
+    pub fn load() -> io::Result<Self> {
+        let mut masks = Self::default();
+        for &(texture, clips) in COLLIDING_CLIPS {
+            let path = texture.asset_path();
+            let image = Image::load_bmp(Path::new(path))
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+            masks.add_sheet(texture, &image, clips);
+        }
+        Ok(masks)
+    }
+
+    /// Add masks for `clips` of a sprite sheet
+    pub fn add_sheet(&mut self, texture: TextureId, image: &Image, clips: &[&[ClipRect]]) {
+        for &clip in clips.iter().copied().flatten() {
+            self.insert(texture, clip, Mask::from_image(image, clip));
+        }
+    }
+
+    pub fn insert(&mut self, texture: TextureId, clip: ClipRect, mask: Mask) {
+        self.masks.insert((texture, clip), mask);
+    }
+
+    pub fn get(&self, texture: TextureId, clip: ClipRect) -> Option<&Mask> {
+        self.masks.get(&(texture, clip))
+    }
+
+    /// Whether two sprites share an opaque pixel
+    /// A sprite without a mask counts as solid, so the hitbox test decides
+    pub fn sprites_overlap(&self, a: Sprite, b: Sprite) -> bool {
+        match (self.get(a.texture, a.clip), self.get(b.texture, b.clip)) {
+            (Some(mask_a), Some(mask_b)) => mask_a.overlaps((a.x, a.y), mask_b, (b.x, b.y)),
+            _ => true,
+        }
+    }
+}
+
+/// Sheets and clips the game tests collisions against
+const COLLIDING_CLIPS: &[(TextureId, &[&[ClipRect]])] = &[
+    (
+        TextureId::Otter,
+        &[
+            OTTER_CLIPS_DOWN,
+            OTTER_CLIPS_UP,
+            OTTER_CLIPS_WALK,
+            OTTER_CLIPS_ROLL,
+        ],
+    ),
+    (TextureId::Missile, &[MISSILE_CLIPS_SHOOT]),
+    (TextureId::Coin, &[COIN_CLIPS_SPIN]),
+    (TextureId::Fish, &[FISH_CLIPS_SWIM]),
+    (TextureId::Seabed, &[ROCK_CLIPS, CRAB_CLIPS_WALK]),
+    (
+        TextureId::PowerUps,
+        &[&[
+            powerup::MAGNET_CLIP,
+            powerup::SHIELD_CLIP,
+            powerup::SLOW_MOTION_CLIP,
+        ]],
+    ),
+];
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K: [u8; 4] = [255, 0, 255, 255];
+    const W: [u8; 4] = [255, 255, 255, 255];
+    const CLEAR: [u8; 4] = [255, 255, 255, 0];
+
+    /// 2x2 bitmap, bottom-up, 24 bits per pixel (rows padded to 8 bytes)
+    fn tiny_bmp() -> Vec<u8> {
+        let mut bytes = b"BM".to_vec();
+        bytes.extend_from_slice(&(54u32 + 16).to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&54u32.to_le_bytes());
+        bytes.extend_from_slice(&40u32.to_le_bytes());
+        bytes.extend_from_slice(&2i32.to_le_bytes());
+        bytes.extend_from_slice(&2i32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&24u16.to_le_bytes());
+        bytes.extend_from_slice(&[0; 24]);
+        // Bottom row: white, magenta; top row: magenta, white (BGR)
+        bytes.extend_from_slice(&[255, 255, 255, 255, 0, 255, 0, 0]);
+        bytes.extend_from_slice(&[255, 0, 255, 255, 255, 255, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_bmp() {
+        let image = Image::from_bmp(&tiny_bmp()).unwrap();
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(image.pixel(0, 0), Some(K));
+        assert_eq!(image.pixel(1, 0), Some(W));
+        assert_eq!(image.pixel(0, 1), Some(W));
+        assert_eq!(image.pixel(2, 0), None);
+
+        assert!(Image::from_bmp(b"nope").is_err());
+        let bytes = tiny_bmp();
+        assert!(Image::from_bmp(&bytes[..bytes.len() - 4]).is_err());
+    }
+
+    #[test]
+    fn test_mask_overlap() {
+        // Diagonal from top-left to bottom-right
+        let image = Image::new(2, 2, vec![W, K, CLEAR, W]);
+        let mask = Mask::from_image(&image, ClipRect::new(0, 0, 2, 2));
+        assert!(mask.is_solid(0, 0) && !mask.is_solid(1, 0));
+
+        assert!(mask.overlaps((0, 0), &mask, (1, 1)));
+        // Offset sideways, only the transparent corners line up
+        assert!(!mask.overlaps((0, 0), &mask, (1, 0)));
+        assert!(!mask.overlaps((0, 0), &mask, (2, 2)));
+    }
+
+    #[test]
+    fn test_load_sprite_sheets() {
+        let masks = CollisionMasks::load().unwrap();
+        let mask = masks.get(TextureId::Otter, OTTER_CLIPS_DOWN[0]).unwrap();
+        assert_eq!((mask.width, mask.height), (33, 32));
+        let solid = mask.solid.iter().filter(|&&s| s).count();
+        assert!(solid > 0 && solid < mask.solid.len());
+        assert!(masks.get(TextureId::Fish, FISH_CLIPS_SWIM[2]).is_some());
+    }
+}
@@ -0,0 +1,240 @@
+//! Timed power-up effects
+//!
+//! `PowerUps` tracks every effect the otter currently has. Each one runs
+//! until its `Expiry`: a score threshold, like the original fish
+//! invincibility, or a number of frames. `Game` starts effects when the
+//! otter eats a fish or touches a `PowerUpPickup`, and ends them through
+//! `PowerUps::tick`.
+
+use crate::render::TextureId;
+use crate::*;
+
+// Power-up sprite clips (powerups.bmp)
+pub const MAGNET_CLIP: ClipRect = ClipRect::new(0, 0, 20, 20);
+pub const SHIELD_CLIP: ClipRect = ClipRect::new(20, 0, 20, 20);
+pub const SLOW_MOTION_CLIP: ClipRect = ClipRect::new(40, 0, 20, 20);
+
+/// Coins whose centers are within this many pixels of the otter's are pulled in
+pub const MAGNET_RADIUS: i32 = 120;
+/// How far a magnetized coin moves towards the otter per frame, on each axis
+pub const MAGNET_PULL: i32 = 12;
+
+/// Hazards move at `1 / SLOW_MOTION_DIVISOR` of their speed in slow motion
+pub const SLOW_MOTION_DIVISOR: i32 = 2;
+
+/// A hazard's speed for this frame, never slowed all the way to a standstill
+pub fn slowed_velocity(velocity: i32, slowed: bool) -> i32 {
+    if slowed && velocity != 0 {
+        (velocity / SLOW_MOTION_DIVISOR).max(1)
+    } else {
+        velocity
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PowerUp {
+    /// From eating a fish: roll through missiles for a bonus
+    Invincibility,
+    /// Pull nearby coins towards the otter
+    Magnet,
+    /// Survive one hit that would end the run
+    Shield,
+    /// Hazards move at half speed
+    SlowMotion,
+}
+
+impl PowerUp {
+    /// Power-ups that spawn as `PowerUpPickup`s (invincibility comes from fish)
+    pub const PICKUPS: &'static [PowerUp] =
+        &[PowerUp::Magnet, PowerUp::Shield, PowerUp::SlowMotion];
+
+    /// Sound played when the effect starts
+    pub fn sound(self) -> SoundEffect {
+        match self {
+            PowerUp::Invincibility => SoundEffect::Powerup,
+            PowerUp::Magnet => SoundEffect::Magnet,
+            PowerUp::Shield => SoundEffect::Shield,
+            PowerUp::SlowMotion => SoundEffect::SlowMotion,
+        }
+    }
+
+    /// Sprite drawn for the pickup and on the HUD while active
+    pub fn icon(self) -> (TextureId, ClipRect) {
+        match self {
+            PowerUp::Invincibility => (TextureId::Fish, FISH_CLIPS_SWIM[0]),
+            PowerUp::Magnet => (TextureId::PowerUps, MAGNET_CLIP),
+            PowerUp::Shield => (TextureId::PowerUps, SHIELD_CLIP),
+            PowerUp::SlowMotion => (TextureId::PowerUps, SLOW_MOTION_CLIP),
+        }
+    }
+}
+
+/// When an effect ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiry {
+    /// Once the score reaches this
+    AtScore(u32),
+    /// After this many more frames
+    AfterFrames(u32),
+}
+
+/// The effects currently active, at most one of each kind
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PowerUps {
+    active: Vec<(PowerUp, Expiry)>,
+}
+
+impl PowerUps {
+    /// Start an effect, or restart it with the new expiry if already active
+    pub fn activate(&mut self, power_up: PowerUp, expiry: Expiry) {
+        self.deactivate(power_up);
+        self.active.push((power_up, expiry));
+    }
+
+    /// End an effect early; returns whether it was active
+    pub fn deactivate(&mut self, power_up: PowerUp) -> bool {
+        let len = self.active.len();
+        self.active.retain(|&(p, _)| p != power_up);
+        self.active.len() != len
+    }
+
+    pub fn is_active(&self, power_up: PowerUp) -> bool {
+        self.expiry(power_up).is_some()
+    }
+
+    pub fn expiry(&self, power_up: PowerUp) -> Option<Expiry> {
+        self.active
+            .iter()
+            .find(|&&(p, _)| p == power_up)
+            .map(|&(_, expiry)| expiry)
+    }
+
+    /// Active effects, in the order they started
+    pub fn iter(&self) -> impl Iterator<Item = (PowerUp, Expiry)> + '_ {
+        self.active.iter().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    /// Count down one frame at `score`; returns the effects that just ran out
+    pub fn tick(&mut self, score: u32) -> Vec<PowerUp> {
+        let mut expired = Vec::new();
+        self.active.retain_mut(|(power_up, expiry)| {
+            let done = match expiry {
+                Expiry::AtScore(end) => score >= *end,
+                Expiry::AfterFrames(frames) => {
+                    *frames = frames.saturating_sub(1);
+                    *frames == 0
+                }
+            };
+            if done {
+                expired.push(*power_up);
+            }
+            !done
+        });
+        expired
+    }
+}
+
+/// A floating power-up, drifting across like a fish
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerUpPickup {
+    pub motion: Motion,
+    pub velocity_x: i32,
+    pub power_up: PowerUp,
+    pub active: bool,
+}
+
+impl PowerUpPickup {
+    pub fn new(power_up: PowerUp, y: i32, velocity_x: i32) -> Self {
+        Self {
+            motion: Motion::at(SCREEN_WIDTH, y),
+            velocity_x,
+            power_up,
+            active: true,
+        }
+    }
+}
+
+impl Entity for PowerUpPickup {
+    fn update(&mut self) {
+        self.hold_position();
+        self.motion.x -= self.velocity_x;
+
+        if self.motion.x < -40 {
+            self.active = false;
+        }
+    }
+
+    fn get_clip(&self) -> ClipRect {
+        self.power_up.icon().1
+    }
+
+    fn get_collision_rect(&self) -> Rect {
+        Rect::new(self.motion.x, self.motion.y, 20, 20)
+    }
+
+    fn texture(&self) -> TextureId {
+        self.power_up.icon().0
+    }
+
+    fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn depth(&self) -> i32 {
+        1
+    }
+
+    fn contact(&self, _otter: &Otter) -> Option<Contact> {
+        self.active.then_some(Contact::PowerUp(self.power_up))
+    }
+
+    fn on_contact(&mut self) {
+        self.active = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_models() {
+        let mut power_ups = PowerUps::default();
+        power_ups.activate(PowerUp::Invincibility, Expiry::AtScore(100));
+        power_ups.activate(PowerUp::Magnet, Expiry::AfterFrames(2));
+
+        assert_eq!(power_ups.tick(50), vec![]);
+        assert_eq!(power_ups.tick(60), vec![PowerUp::Magnet]);
+        assert!(power_ups.is_active(PowerUp::Invincibility));
+        assert_eq!(power_ups.tick(100), vec![PowerUp::Invincibility]);
+        assert_eq!(power_ups.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_reactivating_restarts_timer() {
+        let mut power_ups = PowerUps::default();
+        power_ups.activate(PowerUp::Shield, Expiry::AfterFrames(1));
+        power_ups.activate(PowerUp::Shield, Expiry::AfterFrames(5));
+        assert_eq!(power_ups.iter().count(), 1);
+        assert_eq!(power_ups.tick(0), vec![]);
+        assert_eq!(
+            power_ups.expiry(PowerUp::Shield),
+            Some(Expiry::AfterFrames(4))
+        );
+
+        assert!(power_ups.deactivate(PowerUp::Shield));
+        assert!(!power_ups.deactivate(PowerUp::Shield));
+    }
+}
@@ -0,0 +1,196 @@
+//! Axis-aligned rectangles for hitboxes and screen geometry
+//!
+//! Edges are half-open: a rect covers `x..x + w` and `y..y + h`, so two
+//! rects that only share an edge don't overlap.
+
+use crate::ClipRect;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Rect {
+    pub const fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Rect spanning from (left, top) up to, but not including, (right, bottom)
+    /// An inverted span gives an empty rect
+    pub fn from_edges(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self::new(
+            left,
+            top,
+            (right - left).max(0) as u32,
+            (bottom - top).max(0) as u32,
+        )
+    }
+
+    pub fn left(&self) -> i32 {
+        self.x
+    }
+
+    pub fn top(&self) -> i32 {
+        self.y
+    }
+
+    /// One past the rightmost column
+    pub fn right(&self) -> i32 {
+        self.x + self.w as i32
+    }
+
+    /// One past the bottom row
+    pub fn bottom(&self) -> i32 {
+        self.y + self.h as i32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.w == 0 || self.h == 0
+    }
+
+    pub fn area(&self) -> u32 {
+        self.w * self.h
+    }
+
+    /// Center point, rounded towards the top-left
+    pub fn center(&self) -> (i32, i32) {
+        (self.x + self.w as i32 / 2, self.y + self.h as i32 / 2)
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left() && x < self.right() && y >= self.top() && y < self.bottom()
+    }
+
+    /// Whether `other` lies entirely inside this rect
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() >= self.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Whether the two rects share any area
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.left() < other.right()
+            && self.right() > other.left()
+            && self.top() < other.bottom()
+            && self.bottom() > other.top()
+    }
+
+    /// The shared area, if any
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect::from_edges(
+            self.left().max(other.left()),
+            self.top().max(other.top()),
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        ))
+    }
+
+    /// Smallest rect covering both
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::from_edges(
+            self.left().min(other.left()),
+            self.top().min(other.top()),
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        )
+    }
+
+    /// Size of the shared area in pixels (0 if they don't overlap)
+    pub fn overlap_area(&self, other: &Rect) -> u32 {
+        self.intersection(other).map_or(0, |r| r.area())
+    }
+
+    /// The rect grown by `margin` on every side (shrunk if negative)
+    pub fn expand(&self, margin: i32) -> Rect {
+        Rect::from_edges(
+            self.left() - margin,
+            self.top() - margin,
+            self.right() + margin,
+            self.bottom() + margin,
+        )
+    }
+
+    /// The same rect moved by (dx, dy)
+    pub fn translate(&self, dx: i32, dy: i32) -> Rect {
+        Rect::new(self.x + dx, self.y + dy, self.w, self.h)
+    }
+}
+
+impl From<ClipRect> for Rect {
+    fn from(clip: ClipRect) -> Self {
+        Rect::new(clip.x, clip.y, clip.w, clip.h)
+    }
+}
+
+impl From<Rect> for ClipRect {
+    fn from(rect: Rect) -> Self {
+        ClipRect::new(rect.x, rect.y, rect.w, rect.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_and_union() {
+        let a = Rect::new(0, 0, 10, 10);
+        let b = Rect::new(5, 5, 10, 10);
+        assert_eq!(a.intersection(&b), Some(Rect::new(5, 5, 5, 5)));
+        assert_eq!(a.overlap_area(&b), 25);
+        assert_eq!(a.union(&b), Rect::new(0, 0, 15, 15));
+
+        // Sharing an edge isn't overlapping
+        let c = Rect::new(10, 0, 10, 10);
+        assert!(!a.intersects(&c));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.overlap_area(&c), 0);
+    }
+
+    #[test]
+    fn test_contains_and_center() {
+        let r = Rect::new(-4, 2, 8, 6);
+        assert!(r.contains(-4, 2));
+        assert!(!r.contains(4, 2));
+        assert!(!r.contains(0, 8));
+        assert_eq!(r.center(), (0, 5));
+        assert!(r.contains_rect(&Rect::new(-2, 3, 2, 2)));
+        assert!(!r.contains_rect(&r.translate(1, 0)));
+    }
+
+    #[test]
+    fn test_translate_and_edges() {
+        let r = Rect::new(1, 2, 3, 4);
+        assert_eq!(r.translate(-1, 5), Rect::new(0, 7, 3, 4));
+        assert_eq!((r.right(), r.bottom()), (4, 6));
+        assert_eq!(Rect::from_edges(5, 5, 2, 9), Rect::new(5, 5, 0, 4));
+    }
+
+    #[test]
+    fn test_expand() {
+        let r = Rect::new(10, 10, 4, 6);
+        assert_eq!(r.expand(2), Rect::new(8, 8, 8, 10));
+        assert_eq!(r.expand(-1), Rect::new(11, 11, 2, 4));
+        assert!(r.expand(-3).is_empty());
+
+        // A rect just beyond the edge only meets the expanded one
+        let beside = Rect::new(15, 10, 4, 4);
+        assert!(!r.intersects(&beside));
+        assert!(r.expand(2).intersects(&beside));
+    }
+
+    #[test]
+    fn test_clip_rect_conversion() {
+        let clip = ClipRect::new(12, 32, 480, 320);
+        let rect = Rect::from(clip);
+        assert_eq!(ClipRect::from(rect), clip);
+    }
+}
@@ -0,0 +1,671 @@
+//! Backend-agnostic rendering
+//!
+//! `Game::render_commands` turns the current game state into an ordered
+//! list of sprite copies. A backend only has to load the textures listed
+//! in `TextureId` and execute the commands in order.
+//!
+//! The game ticks at `FRAMES_PER_SECOND`; a backend drawing more often can
+//! use `Game::render_commands_interpolated` to place entities between
+//! their previous and current tick positions.
+
+use crate::*;
+
+/// Start menu screen in menuScreens.bmp
+pub const MENU_START_CLIP: ClipRect =
+    ClipRect::new(12, 32, SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32);
+
+/// Game over screen in menuScreens.bmp
+pub const MENU_GAME_OVER_CLIP: ClipRect =
+    ClipRect::new(505, 32, SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32);
+
+/// The whole screen, as a destination rect
+pub const SCREEN_RECT: ClipRect = ClipRect::new(0, 0, SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32);
+
+// Score placement
+pub const SCORE_MARGIN_RIGHT: i32 = 10;
+pub const SCORE_Y: i32 = 5;
+pub const GAME_OVER_SCORE_Y: i32 = 135;
+pub const GAME_OVER_HIGH_SCORE_Y: i32 = 175;
+
+// Game over breakdown: cause and lifetime totals above "Your score was",
+// run statistics in two columns below "Press SPACE to play again!"
+pub const GAME_OVER_CAUSE_Y: i32 = 16;
+pub const GAME_OVER_LIFETIME_Y: i32 = 34;
+pub const GAME_OVER_STATS_Y: i32 = 230;
+pub const GAME_OVER_STATS_ROW_HEIGHT: i32 = 18;
+pub const GAME_OVER_STATS_LEFT_X: i32 = 48;
+pub const GAME_OVER_STATS_RIGHT_X: i32 = 264;
+/// Combo multiplier, right-aligned under the score
+pub const COMBO_Y: i32 = 38;
+
+// Active power-up icons, in a row from the top-left corner
+pub const POWER_UP_HUD_X: i32 = 10;
+pub const POWER_UP_HUD_Y: i32 = 5;
+pub const POWER_UP_HUD_SPACING: i32 = 36;
+
+// Menu hint and leaderboard table placement
+pub const MENU_HINT_Y: i32 = 268;
+pub const LEADERBOARD_TITLE_Y: i32 = 16;
+pub const LEADERBOARD_FIRST_ROW_Y: i32 = 48;
+pub const LEADERBOARD_ROW_HEIGHT: i32 = 24;
+pub const LEADERBOARD_RANK_X: i32 = 48;
+pub const LEADERBOARD_INITIALS_X: i32 = 96;
+/// Scores are right-aligned against this x
+pub const LEADERBOARD_SCORE_RIGHT_X: i32 = 280;
+pub const LEADERBOARD_DATE_X: i32 = 310;
+
+// Initials entry placement (letters are drawn at 3x scale)
+pub const INITIALS_SCALE: u32 = 3;
+pub const INITIALS_TITLE_Y: i32 = 40;
+pub const INITIALS_SCORE_Y: i32 = 80;
+pub const INITIALS_PROMPT_Y: i32 = 140;
+pub const INITIALS_LETTERS_Y: i32 = 175;
+pub const INITIALS_HINT_Y: i32 = 260;
+
+// Pause overlay and resume countdown placement (title and countdown at 3x scale)
+pub const PAUSE_SCALE: u32 = 3;
+pub const PAUSE_TITLE_Y: i32 = 110;
+pub const PAUSE_HINT_Y: i32 = 180;
+pub const RESUME_COUNTDOWN_Y: i32 = 130;
+
+/// Every texture the game draws from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextureId {
+    Background,
+    Menu,
+    Otter,
+    Missile,
+    Coin,
+    Fish,
+    Seabed,
+    PowerUps,
+    Numbers,
+    Font,
+}
+
+impl TextureId {
+    pub const ALL: &'static [TextureId] = &[
+        TextureId::Background,
+        TextureId::Menu,
+        TextureId::Otter,
+        TextureId::Missile,
+        TextureId::Coin,
+        TextureId::Fish,
+        TextureId::Seabed,
+        TextureId::PowerUps,
+        TextureId::Numbers,
+        TextureId::Font,
+    ];
+
+    /// Path of the (magenta-keyed) bitmap, relative to the working directory
+    pub fn asset_path(&self) -> &'static str {
+        match self {
+            TextureId::Background => "assets/background.bmp",
+            TextureId::Menu => "assets/menuScreens.bmp",
+            TextureId::Otter => "assets/otter.bmp",
+            TextureId::Missile => "assets/missiles.bmp",
+            TextureId::Coin => "assets/coins.bmp",
+            TextureId::Fish => "assets/LoveFish.bmp",
+            TextureId::Seabed => "assets/seabed.bmp",
+            TextureId::PowerUps => "assets/powerups.bmp",
+            TextureId::Numbers => "assets/numbers.bmp",
+            TextureId::Font => "assets/font.bmp",
+        }
+    }
+}
+
+/// Draw layers, from back to front
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    Background,
+    Screen,
+    Entities,
+    Player,
+    Hud,
+}
+
+/// Copy `src` of `texture` (the whole texture if `None`) to `dest`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawCommand {
+    pub texture: TextureId,
+    pub src: Option<ClipRect>,
+    pub dest: ClipRect,
+    pub layer: Layer,
+}
+
+impl DrawCommand {
+    /// Draw a sprite clip unscaled with its top-left corner at (x, y)
+    pub fn sprite(texture: TextureId, clip: ClipRect, x: i32, y: i32, layer: Layer) -> Self {
+        Self {
+            texture,
+            src: Some(clip),
+            dest: ClipRect::new(x, y, clip.w, clip.h),
+            layer,
+        }
+    }
+}
+
+/// Width in pixels of a number drawn with `NUMBER_CLIPS`
+pub fn digits_width(digits: &[u8]) -> i32 {
+    digits.len() as i32 * DIGIT_SPACING
+}
+
+/// Append commands drawing `digits` starting at (x, y)
+pub fn push_digits(commands: &mut Vec<DrawCommand>, digits: &[u8], x: i32, y: i32) {
+    let mut current_x = x;
+    for &digit in digits {
+        let clip = NUMBER_CLIPS[digit as usize];
+        commands.push(DrawCommand::sprite(
+            TextureId::Numbers,
+            clip,
+            current_x,
+            y,
+            Layer::Hud,
+        ));
+        current_x += DIGIT_SPACING;
+    }
+}
+
+/// Width in pixels of font text
+pub fn text_width(text: &str) -> i32 {
+    text.chars().count() as i32 * GLYPH_SPACING
+}
+
+/// Append commands drawing font text starting at (x, y), scaled up by `scale`
+pub fn push_text_scaled(commands: &mut Vec<DrawCommand>, text: &str, x: i32, y: i32, scale: u32) {
+    let mut current_x = x;
+    for c in text.chars() {
+        if let Some(clip) = glyph_clip(c) {
+            commands.push(DrawCommand {
+                texture: TextureId::Font,
+                src: Some(clip),
+                dest: ClipRect::new(current_x, y, clip.w * scale, clip.h * scale),
+                layer: Layer::Hud,
+            });
+        }
+        current_x += GLYPH_SPACING * scale as i32;
+    }
+}
+
+/// Append commands drawing font text starting at (x, y)
+pub fn push_text(commands: &mut Vec<DrawCommand>, text: &str, x: i32, y: i32) {
+    push_text_scaled(commands, text, x, y, 1);
+}
+
+/// Append commands drawing font text horizontally centered on the screen
+pub fn push_text_centered(commands: &mut Vec<DrawCommand>, text: &str, y: i32) {
+    push_text(commands, text, (SCREEN_WIDTH - text_width(text)) / 2, y);
+}
+
+/// Position `alpha` of the way from `prev` to `current`, rounded to a pixel
+pub fn lerp(prev: i32, current: i32, alpha: f32) -> i32 {
+    prev + ((current - prev) as f32 * alpha).round() as i32
+}
+
+impl Game {
+    /// Draw instructions for the current tick, in the order they must be executed
+    pub fn render_commands(&self) -> Vec<DrawCommand> {
+        self.render_commands_interpolated(1.0)
+    }
+
+    /// Draw instructions with entities placed `alpha` (0.0 to 1.0) of the way
+    /// from their previous tick's position to the current one
+    pub fn render_commands_interpolated(&self, alpha: f32) -> Vec<DrawCommand> {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let mut commands = vec![DrawCommand {
+            texture: TextureId::Background,
+            src: None,
+            dest: SCREEN_RECT,
+            layer: Layer::Background,
+        }];
+
+        match self.state {
+            _ if self.show_leaderboard => self.push_leaderboard(&mut commands),
+
+            GameState::Menu => {
+                commands.push(DrawCommand::sprite(
+                    TextureId::Menu,
+                    MENU_START_CLIP,
+                    0,
+                    0,
+                    Layer::Screen,
+                ));
+                push_text_centered(&mut commands, "TAB - HIGH SCORES", MENU_HINT_Y);
+            }
+
+            GameState::GameOver if self.is_entering_initials() => {
+                self.push_initials_entry(&mut commands);
+            }
+
+            GameState::Playing => {
+                self.push_playfield(&mut commands, alpha);
+
+                // Seconds left before a resumed run gets going again
+                if self.resume_countdown > 0 {
+                    let seconds = self.resume_countdown.div_ceil(FRAMES_PER_SECOND);
+                    let text = seconds.to_string();
+                    let width = text_width(&text) * PAUSE_SCALE as i32;
+                    push_text_scaled(
+                        &mut commands,
+                        &text,
+                        (SCREEN_WIDTH - width) / 2,
+                        RESUME_COUNTDOWN_Y,
+                        PAUSE_SCALE,
+                    );
+                }
+            }
+
+            GameState::Paused => {
+                self.push_playfield(&mut commands, alpha);
+
+                let width = text_width("PAUSED") * PAUSE_SCALE as i32;
+                push_text_scaled(
+                    &mut commands,
+                    "PAUSED",
+                    (SCREEN_WIDTH - width) / 2,
+                    PAUSE_TITLE_Y,
+                    PAUSE_SCALE,
+                );
+                push_text_centered(&mut commands, "P OR ENTER - RESUME", PAUSE_HINT_Y);
+            }
+
+            GameState::GameOver => {
+                commands.push(DrawCommand::sprite(
+                    TextureId::Menu,
+                    MENU_GAME_OVER_CLIP,
+                    0,
+                    0,
+                    Layer::Screen,
+                ));
+
+                // Final score centered, high score below
+                let digits = self.get_score_digits();
+                push_digits(
+                    &mut commands,
+                    &digits,
+                    (SCREEN_WIDTH - digits_width(&digits)) / 2,
+                    GAME_OVER_SCORE_Y,
+                );
+
+                let digits = self.get_high_score_digits();
+                push_digits(
+                    &mut commands,
+                    &digits,
+                    (SCREEN_WIDTH - digits_width(&digits)) / 2,
+                    GAME_OVER_HIGH_SCORE_Y,
+                );
+
+                self.push_game_over_stats(&mut commands);
+            }
+        }
+
+        commands
+    }
+
+    /// Entities, otter and running score, as drawn during play
+    fn push_playfield(&self, commands: &mut Vec<DrawCommand>, alpha: f32) {
+        let mut entities: Vec<&AnyEntity> = self.entities.iter().collect();
+        entities.sort_by_key(|e| e.depth());
+        for entity in entities {
+            let (prev_x, prev_y) = entity.prev_position();
+            let (x, y) = entity.position();
+            commands.push(DrawCommand::sprite(
+                entity.texture(),
+                entity.get_clip(),
+                lerp(prev_x, x, alpha),
+                lerp(prev_y, y, alpha),
+                Layer::Entities,
+            ));
+        }
+
+        // Source clip dimensions, unscaled (matches original SDL1.2 behavior)
+        commands.push(DrawCommand::sprite(
+            TextureId::Otter,
+            self.otter.get_clip(),
+            lerp(self.otter.motion.prev_x, self.otter.motion.x, alpha),
+            lerp(self.otter.motion.prev_y, self.otter.motion.y, alpha),
+            Layer::Player,
+        ));
+
+        // Score in top-right corner
+        let digits = self.get_score_digits();
+        let x = SCREEN_WIDTH - digits_width(&digits) - SCORE_MARGIN_RIGHT;
+        push_digits(commands, &digits, x, SCORE_Y);
+
+        // Combo multiplier under it, while a combo is going
+        if self.combo_multiplier() > 1 {
+            let text = format!("X{}", self.combo_multiplier());
+            let x = SCREEN_WIDTH - text_width(&text) - SCORE_MARGIN_RIGHT;
+            push_text(commands, &text, x, COMBO_Y);
+        }
+
+        // Active power-ups in the top-left corner
+        for (i, (power_up, _)) in self.power_ups.iter().enumerate() {
+            let (texture, clip) = power_up.icon();
+            commands.push(DrawCommand::sprite(
+                texture,
+                clip,
+                POWER_UP_HUD_X + i as i32 * POWER_UP_HUD_SPACING,
+                POWER_UP_HUD_Y,
+                Layer::Hud,
+            ));
+        }
+    }
+
+    /// How the run went, and how all runs so far have gone
+    fn push_game_over_stats(&self, commands: &mut Vec<DrawCommand>) {
+        let stats = &self.stats;
+        if let Some(cause) = stats.cause_of_death {
+            push_text_centered(commands, cause.description(), GAME_OVER_CAUSE_Y);
+        }
+
+        // Two short lines, since the totals only ever grow
+        let lifetime = &self.lifetime;
+        let runs = format!("LIFETIME RUNS {}", lifetime.runs);
+        let totals = format!(
+            "COINS {} FISH {}",
+            lifetime.coins_collected, lifetime.fish_eaten
+        );
+        push_text_centered(commands, &runs, GAME_OVER_LIFETIME_Y);
+        push_text_centered(
+            commands,
+            &totals,
+            GAME_OVER_LIFETIME_Y + GAME_OVER_STATS_ROW_HEIGHT,
+        );
+
+        let seconds = |frames: u32| frames as f32 / FRAMES_PER_SECOND as f32;
+        let rows = [
+            (
+                format!("FRAMES {}", stats.frames),
+                format!("WALKING {:.1} SEC", seconds(stats.walking_frames)),
+            ),
+            (
+                format!("COINS {}", stats.coins_collected),
+                format!("MISSED {}", stats.coins_missed),
+            ),
+            (
+                format!("BEST STREAK {}", stats.best_streak),
+                format!("FISH {}", stats.fish_eaten),
+            ),
+            (
+                format!("DODGED {}", stats.missiles_dodged),
+                format!("NEAR MISSES {}", stats.near_misses),
+            ),
+            (
+                format!("DESTROYED {}", stats.missiles_destroyed),
+                String::new(),
+            ),
+        ];
+        for (i, (left, right)) in rows.iter().enumerate() {
+            let y = GAME_OVER_STATS_Y + i as i32 * GAME_OVER_STATS_ROW_HEIGHT;
+            push_text(commands, left, GAME_OVER_STATS_LEFT_X, y);
+            push_text(commands, right, GAME_OVER_STATS_RIGHT_X, y);
+        }
+    }
+
+    /// Top-10 table: rank, initials, score and date
+    fn push_leaderboard(&self, commands: &mut Vec<DrawCommand>) {
+        push_text_centered(commands, "HIGH SCORES", LEADERBOARD_TITLE_Y);
+
+        let entries = self.leaderboard.entries();
+        if entries.is_empty() {
+            push_text_centered(commands, "NO SCORES YET", LEADERBOARD_FIRST_ROW_Y);
+        }
+
+        for (rank, entry) in entries.iter().enumerate() {
+            let y = LEADERBOARD_FIRST_ROW_Y + rank as i32 * LEADERBOARD_ROW_HEIGHT;
+            // Mark the run that was just entered
+            let marker = if self.last_rank == Some(rank) {
+                ">"
+            } else {
+                ""
+            };
+            let rank_text = format!("{}{}.", marker, rank + 1);
+            push_text(
+                commands,
+                &rank_text,
+                LEADERBOARD_RANK_X + GLYPH_SPACING * 2 - text_width(&rank_text),
+                y,
+            );
+            push_text(commands, &entry.initials, LEADERBOARD_INITIALS_X, y);
+            let score = entry.score.to_string();
+            push_text(
+                commands,
+                &score,
+                LEADERBOARD_SCORE_RIGHT_X - text_width(&score),
+                y,
+            );
+            push_text(commands, &entry.date, LEADERBOARD_DATE_X, y);
+        }
+    }
+
+    /// Arcade-style initials entry with the current letter underlined
+    fn push_initials_entry(&self, commands: &mut Vec<DrawCommand>) {
+        let Some(entry) = &self.initials_entry else {
+            return;
+        };
+
+        push_text_centered(commands, "NEW HIGH SCORE!", INITIALS_TITLE_Y);
+        let digits = self.get_score_digits();
+        push_digits(
+            commands,
+            &digits,
+            (SCREEN_WIDTH - digits_width(&digits)) / 2,
+            INITIALS_SCORE_Y,
+        );
+        push_text_centered(commands, "ENTER YOUR INITIALS", INITIALS_PROMPT_Y);
+
+        let advance = GLYPH_SPACING * INITIALS_SCALE as i32;
+        let x = (SCREEN_WIDTH - advance * leaderboard::INITIALS_LEN as i32) / 2;
+        let initials = entry.initials();
+        push_text_scaled(commands, &initials, x, INITIALS_LETTERS_Y, INITIALS_SCALE);
+        let cursor_x = x + advance * entry.cursor as i32;
+        let cursor_y = INITIALS_LETTERS_Y + FONT_GLYPH_HEIGHT as i32 * INITIALS_SCALE as i32;
+        push_text_scaled(commands, "^", cursor_x, cursor_y, INITIALS_SCALE);
+
+        push_text_centered(commands, "UP/DOWN OR SPACE - CHANGE", INITIALS_HINT_Y);
+        push_text_centered(
+            commands,
+            "ENTER OR HOLD SPACE - NEXT",
+            INITIALS_HINT_Y + LEADERBOARD_ROW_HEIGHT,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_commands() {
+        let game = Game::with_seed(0);
+        let commands = game.render_commands();
+        assert_eq!(commands[0].texture, TextureId::Background);
+        assert_eq!(commands[1].src, Some(MENU_START_CLIP));
+        assert_eq!(commands[1].dest, SCREEN_RECT);
+        assert!(commands[2..].iter().all(|c| c.texture == TextureId::Font));
+    }
+
+    #[test]
+    fn test_commands_sorted_by_layer() {
+        // The idle otter walks the seabed, so keep it clear to stay in play
+        let config = GameConfig {
+            rock_spawn_chance: 0,
+            crab_spawn_chance: 0,
+            ..GameConfig::default()
+        };
+        let mut game = Game::with_config(config, 3);
+        game.start();
+        for _ in 0..80 {
+            game.update();
+        }
+        assert_eq!(game.state, GameState::Playing);
+        let commands = game.render_commands();
+        assert!(commands.windows(2).all(|w| w[0].layer <= w[1].layer));
+        assert_eq!(
+            commands
+                .iter()
+                .filter(|c| c.texture == TextureId::Otter)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_text_skips_unknown_glyphs() {
+        let mut commands = Vec::new();
+        push_text(&mut commands, "A B", 10, 20);
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].dest.x, 10 + 2 * GLYPH_SPACING);
+        assert_eq!(commands[1].src, Some(LETTER_CLIPS[1]));
+        assert_eq!(text_width("A B"), 3 * GLYPH_SPACING);
+    }
+
+    #[test]
+    fn test_leaderboard_screen_replaces_menu() {
+        let mut game = Game::with_seed(0);
+        game.toggle_leaderboard();
+        let commands = game.render_commands();
+        assert!(commands.iter().all(|c| c.texture != TextureId::Menu));
+        assert!(commands.iter().any(|c| c.texture == TextureId::Font));
+    }
+
+    #[test]
+    fn test_pause_overlay_and_countdown() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        game.pause();
+        let commands = game.render_commands();
+        assert!(commands.iter().any(|c| c.texture == TextureId::Otter));
+        assert!(commands.iter().any(|c| c.texture == TextureId::Font));
+
+        game.resume();
+        let countdown: Vec<_> = game
+            .render_commands()
+            .into_iter()
+            .filter(|c| c.texture == TextureId::Font)
+            .collect();
+        assert_eq!(countdown.len(), 1);
+        assert_eq!(countdown[0].src, glyph_clip('3'));
+    }
+
+    #[test]
+    fn test_interpolated_positions() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        game.update();
+        let otter_y = |game: &Game, alpha| {
+            game.render_commands_interpolated(alpha)
+                .into_iter()
+                .find(|c| c.texture == TextureId::Otter)
+                .unwrap()
+                .dest
+                .y
+        };
+        let (prev_y, y) = (game.otter.motion.prev_y, game.otter.motion.y);
+        assert_ne!(prev_y, y);
+        assert_eq!(otter_y(&game, 0.0), prev_y);
+        assert_eq!(otter_y(&game, 1.0), y);
+        assert_eq!(otter_y(&game, 0.5), lerp(prev_y, y, 0.5));
+
+        // Nothing moves while paused, so neither does the drawing
+        game.pause();
+        game.update();
+        assert_eq!(otter_y(&game, 0.0), otter_y(&game, 1.0));
+    }
+
+    #[test]
+    fn test_score_right_aligned() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        game.score = 1234;
+        let digits: Vec<_> = game
+            .render_commands()
+            .into_iter()
+            .filter(|c| c.texture == TextureId::Numbers)
+            .collect();
+        assert_eq!(digits.len(), 4);
+        assert_eq!(
+            digits[0].dest.x,
+            SCREEN_WIDTH - 4 * DIGIT_SPACING - SCORE_MARGIN_RIGHT
+        );
+        assert_eq!(digits[0].dest.y, SCORE_Y);
+        assert_eq!(digits[1].src, Some(NUMBER_CLIPS[2]));
+    }
+
+    #[test]
+    fn test_combo_on_hud() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        let font = |game: &Game| {
+            game.render_commands()
+                .into_iter()
+                .filter(|c| c.texture == TextureId::Font)
+                .collect::<Vec<_>>()
+        };
+        assert!(font(&game).is_empty());
+
+        game.coin_streak = 3;
+        let combo = font(&game);
+        assert_eq!(combo.len(), 2);
+        assert_eq!(combo[1].src, glyph_clip('3'));
+        assert_eq!(combo[1].dest.y, COMBO_Y);
+    }
+
+    #[test]
+    fn test_game_over_breakdown() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        game.stats.best_streak = 7;
+        game.stats.cause_of_death = Some(DeathCause::Crab);
+        game.lifetime.runs = 1_000_000;
+        game.lifetime.coins_collected = 1_000_000_000;
+        game.lifetime.fish_eaten = 10_000_000;
+        game.state = GameState::GameOver;
+        let commands = game.render_commands();
+        let glyphs_at = |y, xs: std::ops::Range<i32>| {
+            commands
+                .iter()
+                .filter(|c| c.texture == TextureId::Font)
+                .filter(|c| c.dest.y == y && xs.contains(&c.dest.x))
+                .count()
+        };
+
+        assert_eq!(
+            glyphs_at(GAME_OVER_CAUSE_Y, 0..SCREEN_WIDTH),
+            "NIPPEDBYACRAB".len()
+        );
+        let streak_y = GAME_OVER_STATS_Y + 2 * GAME_OVER_STATS_ROW_HEIGHT;
+        assert_eq!(
+            glyphs_at(streak_y, 0..GAME_OVER_STATS_RIGHT_X),
+            "BESTSTREAK7".len()
+        );
+
+        assert_eq!(
+            glyphs_at(GAME_OVER_LIFETIME_Y, 0..SCREEN_WIDTH),
+            "LIFETIMERUNS1000000".len()
+        );
+
+        // Nothing may run off the screen, even with huge lifetime totals
+        assert!(commands.iter().all(|c| c.dest.x >= 0
+            && c.dest.x + c.dest.w as i32 <= SCREEN_WIDTH
+            && c.dest.y + c.dest.h as i32 <= SCREEN_HEIGHT));
+    }
+
+    #[test]
+    fn test_power_up_icons_on_hud() {
+        let mut game = Game::with_seed(0);
+        game.start();
+        game.start_power_up(PowerUp::Magnet);
+        game.start_power_up(PowerUp::Shield);
+        let icons: Vec<_> = game
+            .render_commands()
+            .into_iter()
+            .filter(|c| c.layer == Layer::Hud && c.texture == TextureId::PowerUps)
+            .collect();
+        assert_eq!(icons.len(), 2);
+        assert_eq!(icons[0].src, Some(PowerUp::Magnet.icon().1));
+        assert_eq!(icons[0].dest.x, POWER_UP_HUD_X);
+        assert_eq!(icons[1].dest.x, POWER_UP_HUD_X + POWER_UP_HUD_SPACING);
+    }
+}
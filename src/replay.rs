@@ -0,0 +1,462 @@
+//! Input recording and deterministic replay
+//!
+//! A replay is the seed of a run plus every input applied to the game,
+//! keyed by the frame (number of `Game::update` calls) it happened on.
+//! Feeding the same inputs into a fresh `Game` with the same seed and
+//! `GameConfig` reproduces the original run exactly, so the replay also
+//! stores the config's fingerprint and refuses to play back against another.
+
+use crate::config::GameConfig;
+use crate::input::InputAction;
+use crate::mask::CollisionMasks;
+use crate::Game;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Magic bytes at the start of every replay file
+const REPLAY_MAGIC: &[u8; 4] = b"OSRP";
+
+/// Current replay file format version
+///
+/// Version 2 replaced version 1's fixed space/pause/resume inputs with
+/// pressed and released `InputAction`s, and version 3 added the config
+/// fingerprint.
+pub const REPLAY_VERSION: u8 = 3;
+
+/// A single recorded input: an action being pressed or released
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayInput {
+    Pressed(InputAction),
+    Released(InputAction),
+}
+
+impl ReplayInput {
+    /// Apply this input to a game
+    pub fn apply(self, game: &mut Game) {
+        match self {
+            ReplayInput::Pressed(action) => game.handle_action(action, true),
+            ReplayInput::Released(action) => game.handle_action(action, false),
+        }
+    }
+
+    /// Twice the action's `action_code`, plus one for a release
+    /// (so a swim press is 0 and a swim release is 1)
+    fn to_byte(self) -> u8 {
+        match self {
+            ReplayInput::Pressed(action) => action_code(action) * 2,
+            ReplayInput::Released(action) => action_code(action) * 2 + 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        let action = action_from_code(byte / 2)?;
+        Some(if byte.is_multiple_of(2) {
+            ReplayInput::Pressed(action)
+        } else {
+            ReplayInput::Released(action)
+        })
+    }
+}
+
+/// Each action's code in replay files; these are part of the file format,
+/// so never reuse or renumber them
+fn action_code(action: InputAction) -> u8 {
+    match action {
+        InputAction::Swim => 0,
+        InputAction::Pause => 1,
+        InputAction::Start => 2,
+        InputAction::Leaderboard => 3,
+        InputAction::Quit => 4,
+    }
+}
+
+fn action_from_code(code: u8) -> Option<InputAction> {
+    match code {
+        0 => Some(InputAction::Swim),
+        1 => Some(InputAction::Pause),
+        2 => Some(InputAction::Start),
+        3 => Some(InputAction::Leaderboard),
+        4 => Some(InputAction::Quit),
+        _ => None,
+    }
+}
+
+/// An input and the frame it was applied on (before that frame's update)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayEvent {
+    pub frame: u32,
+    pub input: ReplayInput,
+}
+
+/// A recorded run: seed, config, inputs and total length in frames
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Replay {
+    pub seed: u64,
+    /// `GameConfig::fingerprint` of the config the run was recorded with
+    pub config_fingerprint: u64,
+    pub frame_count: u32,
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    /// Create a fresh game ready for playback, for a replay recorded with
+    /// the default config
+    pub fn new_game(&self) -> io::Result<Game> {
+        self.new_game_with_config(GameConfig::default(), None)
+    }
+
+    /// Create a fresh game for a replay recorded with custom tuning, using
+    /// `masks` if it was recorded with `precise_collision`
+    /// Fails if `config` isn't the one the replay was recorded with, or
+    /// precise collision is on without masks, since the run would go
+    /// differently
+    pub fn new_game_with_config(
+        &self,
+        config: GameConfig,
+        masks: Option<Arc<CollisionMasks>>,
+    ) -> io::Result<Game> {
+        if config.fingerprint() != self.config_fingerprint {
+            return Err(invalid_data("replay was recorded with a different game config"));
+        }
+        if config.precise_collision && masks.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "replay needs collision masks for precise collision",
+            ));
+        }
+        let mut game = Game::with_config(config, self.seed);
+        game.set_collision_masks(masks);
+        Ok(game)
+    }
+
+    /// Play the whole replay (recorded with the default config) and return
+    /// the final game
+    pub fn run(&self) -> io::Result<Game> {
+        let mut game = self.new_game()?;
+        let mut player = ReplayPlayer::new(self);
+        while player.step(&mut game) {}
+        Ok(game)
+    }
+
+    /// Serialize to the compact binary format
+    ///
+    /// Layout: magic, version, seed (u64 LE), config fingerprint (u64 LE),
+    /// frame count (u32 LE), event count (u32 LE), then per event a varint
+    /// frame delta followed by one input byte.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(29 + self.events.len() * 2);
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.push(REPLAY_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.config_fingerprint.to_le_bytes());
+        bytes.extend_from_slice(&self.frame_count.to_le_bytes());
+        bytes.extend_from_slice(&(self.events.len() as u32).to_le_bytes());
+
+        let mut last_frame = 0;
+        for event in &self.events {
+            write_varint(&mut bytes, event.frame - last_frame);
+            bytes.push(event.input.to_byte());
+            last_frame = event.frame;
+        }
+        bytes
+    }
+
+    /// Parse the compact binary format
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+
+        if reader.take(4)? != REPLAY_MAGIC {
+            return Err(invalid_data("not a replay file"));
+        }
+        let version = reader.take(1)?[0];
+        if version != REPLAY_VERSION {
+            return Err(invalid_data(&format!("unsupported replay version {}", version)));
+        }
+
+        let seed = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let config_fingerprint = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let frame_count = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let event_count = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+
+        let mut events = Vec::new();
+        let mut frame: u32 = 0;
+        for _ in 0..event_count {
+            let delta = reader.varint()?;
+            frame = frame
+                .checked_add(delta)
+                .ok_or_else(|| invalid_data("frame overflow"))?;
+            let input = ReplayInput::from_byte(reader.take(1)?[0])
+                .ok_or_else(|| invalid_data("unknown input"))?;
+            events.push(ReplayEvent { frame, input });
+        }
+
+        if reader.pos != bytes.len() {
+            return Err(invalid_data("trailing data"));
+        }
+
+        Ok(Self {
+            seed,
+            config_fingerprint,
+            frame_count,
+            events,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+}
+
+/// Records inputs during a run
+///
+/// Call `record` alongside every input applied to the game, and
+/// `end_frame` after every `Game::update`.
+#[derive(Clone, Debug)]
+pub struct ReplayRecorder {
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    /// Start recording a game created with (or reset to) `seed` and `config`
+    pub fn new(seed: u64, config: &GameConfig) -> Self {
+        Self {
+            replay: Replay {
+                seed,
+                config_fingerprint: config.fingerprint(),
+                ..Replay::default()
+            },
+        }
+    }
+
+    /// Record an input on the current frame
+    pub fn record(&mut self, input: ReplayInput) {
+        self.replay.events.push(ReplayEvent {
+            frame: self.replay.frame_count,
+            input,
+        });
+    }
+
+    /// Record and apply an input in one go
+    pub fn apply(&mut self, input: ReplayInput, game: &mut Game) {
+        self.record(input);
+        input.apply(game);
+    }
+
+    pub fn end_frame(&mut self) {
+        self.replay.frame_count += 1;
+    }
+
+    /// The replay recorded so far
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+/// Feeds a replay into a game one frame at a time
+#[derive(Clone, Debug)]
+pub struct ReplayPlayer<'a> {
+    replay: &'a Replay,
+    frame: u32,
+    next_event: usize,
+}
+
+impl<'a> ReplayPlayer<'a> {
+    pub fn new(replay: &'a Replay) -> Self {
+        Self {
+            replay,
+            frame: 0,
+            next_event: 0,
+        }
+    }
+
+    /// Apply this frame's inputs and update the game once
+    /// Returns false (without touching the game) once the replay is over
+    pub fn step(&mut self, game: &mut Game) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        while let Some(event) = self.replay.events.get(self.next_event) {
+            if event.frame != self.frame {
+                break;
+            }
+            event.input.apply(game);
+            self.next_event += 1;
+        }
+
+        game.update();
+        self.frame += 1;
+        true
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frame_count
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        let end = self.pos + n;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or_else(|| invalid_data("unexpected end of replay"))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn varint(&mut self) -> io::Result<u32> {
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u32)
+                .checked_shl(shift)
+                .ok_or_else(|| invalid_data("varint overflow"))?;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid_data("varint too long"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_replay() -> Replay {
+        Replay {
+            seed: 0xdead_beef,
+            config_fingerprint: GameConfig::default().fingerprint(),
+            frame_count: 500,
+            events: vec![
+                ReplayEvent { frame: 0, input: ReplayInput::Pressed(InputAction::Swim) },
+                ReplayEvent { frame: 3, input: ReplayInput::Released(InputAction::Swim) },
+                ReplayEvent { frame: 300, input: ReplayInput::Pressed(InputAction::Swim) },
+                ReplayEvent { frame: 300, input: ReplayInput::Released(InputAction::Swim) },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let replay = sample_replay();
+        let bytes = replay.to_bytes();
+        assert_eq!(Replay::from_bytes(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn test_input_bytes_round_trip() {
+        assert_eq!(ReplayInput::Pressed(InputAction::Swim).to_byte(), 0);
+        assert_eq!(ReplayInput::Released(InputAction::Swim).to_byte(), 1);
+        assert_eq!(ReplayInput::Released(InputAction::Pause).to_byte(), 3);
+        assert_eq!(ReplayInput::Pressed(InputAction::Quit).to_byte(), 8);
+        for &action in InputAction::ALL {
+            for input in [ReplayInput::Pressed(action), ReplayInput::Released(action)] {
+                assert_eq!(ReplayInput::from_byte(input.to_byte()), Some(input));
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_is_compact() {
+        // Header is 29 bytes, small deltas take 2 bytes per event
+        assert_eq!(sample_replay().to_bytes().len(), 29 + 2 + 2 + 3 + 2);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let bytes = sample_replay().to_bytes();
+        assert!(Replay::from_bytes(b"nope").is_err());
+        assert!(Replay::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = REPLAY_VERSION + 1;
+        assert!(Replay::from_bytes(&bad_version).is_err());
+
+        // Version 1 inputs meant something else, so old files can't play back
+        let mut old_version = bytes.clone();
+        old_version[4] = 1;
+        assert!(Replay::from_bytes(&old_version).is_err());
+
+        let mut bad_input = bytes;
+        let last = bad_input.len() - 1;
+        bad_input[last] = 0xff;
+        assert!(Replay::from_bytes(&bad_input).is_err());
+    }
+
+    #[test]
+    fn test_refuses_other_config() {
+        let replay = sample_replay();
+        let smooth = GameConfig {
+            otter_physics: crate::config::OtterPhysics::Smooth,
+            ..GameConfig::default()
+        };
+        assert!(replay.new_game_with_config(smooth.clone(), None).is_err());
+
+        let recorded = Replay {
+            config_fingerprint: smooth.fingerprint(),
+            ..replay
+        };
+        assert!(recorded.new_game().is_err());
+        assert!(recorded.new_game_with_config(smooth, None).is_ok());
+    }
+
+    #[test]
+    fn test_precise_collision_needs_masks() {
+        let precise = GameConfig {
+            precise_collision: true,
+            ..GameConfig::default()
+        };
+        let replay = Replay {
+            config_fingerprint: precise.fingerprint(),
+            ..sample_replay()
+        };
+        let result = replay.new_game_with_config(precise, None);
+        assert!(result.is_err_and(|e| e.kind() == io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_player_stops_at_frame_count() {
+        let replay = sample_replay();
+        let mut game = replay.new_game().unwrap();
+        let mut player = ReplayPlayer::new(&replay);
+        let mut steps = 0;
+        while player.step(&mut game) {
+            steps += 1;
+        }
+        assert_eq!(steps, replay.frame_count);
+        assert!(player.is_finished());
+    }
+}
@@ -0,0 +1,192 @@
+//! Persistent save data (high score, leaderboard and lifetime statistics)
+//!
+//! Stored as a small versioned TOML file in the per-user data directory.
+//! Saves are atomic: the new contents are written to a temporary file
+//! next to the save, which is then renamed over it.
+
+use crate::leaderboard::Leaderboard;
+use crate::{Game, LifetimeStats};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Current save file format version
+/// 1: high score only; 2: adds the leaderboard; 3: adds lifetime statistics
+pub const SAVE_VERSION: u32 = 3;
+
+/// File name of the save inside the data directory
+pub const SAVE_FILE_NAME: &str = "save.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    pub high_score: u32,
+    #[serde(default)]
+    pub leaderboard: Leaderboard,
+    #[serde(default)]
+    pub lifetime: LifetimeStats,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            high_score: 0,
+            leaderboard: Leaderboard::default(),
+            lifetime: LifetimeStats::default(),
+        }
+    }
+}
+
+/// Default save location, e.g. `~/.local/share/otter-swag/save.toml`
+pub fn default_save_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("otter-swag").join(SAVE_FILE_NAME))
+}
+
+impl SaveData {
+    /// Load save data; a missing file is not an error and yields the defaults
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        Self::from_toml(&text)
+    }
+
+    pub fn from_toml(text: &str) -> io::Result<Self> {
+        let mut data: Self =
+            toml::from_str(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if data.version > SAVE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("save version {} is newer than supported {}", data.version, SAVE_VERSION),
+            ));
+        }
+        // Don't trust a hand-edited table to be sorted or the right size
+        data.leaderboard = Leaderboard::from_entries(data.leaderboard.entries().to_vec());
+        Ok(data)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("save data is always representable as TOML")
+    }
+
+    /// Atomically replace the file at `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let tmp_path = path.with_extension("toml.tmp");
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(self.to_toml().as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Fold a finished run into the save data
+    pub fn record_run(&mut self, game: &Game) {
+        self.version = SAVE_VERSION;
+        self.high_score = self.high_score.max(game.high_score).max(game.score);
+        self.leaderboard = game.leaderboard.clone();
+        self.lifetime = game.lifetime.clone();
+    }
+
+    /// Seed a new game with the persisted high score, leaderboard and lifetime statistics
+    pub fn apply_to(&self, game: &mut Game) {
+        game.high_score = self.high_score;
+        game.leaderboard = self.leaderboard.clone();
+        game.lifetime = self.lifetime.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leaderboard::LeaderboardEntry;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("otter-swag-test-{}-{}", std::process::id(), name))
+            .join(SAVE_FILE_NAME)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = temp_path("round-trip");
+        let mut data = SaveData {
+            high_score: 4321,
+            ..SaveData::default()
+        };
+        data.leaderboard.insert(LeaderboardEntry {
+            initials: "OTR".to_string(),
+            score: 4321,
+            date: "2024-05-06".to_string(),
+            seed: u64::MAX,
+        });
+        data.save(&path).unwrap();
+        assert_eq!(SaveData::load(&path).unwrap(), data);
+        assert!(!path.with_extension("toml.tmp").exists());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let path = temp_path("missing");
+        assert_eq!(SaveData::load(&path).unwrap(), SaveData::default());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_error() {
+        assert!(SaveData::from_toml("high_score = \"lots\"").is_err());
+        assert!(SaveData::from_toml("\u{0}garbage").is_err());
+    }
+
+    #[test]
+    fn test_loads_version_1() {
+        let data = SaveData::from_toml("version = 1\nhigh_score = 77\n").unwrap();
+        assert_eq!(data.high_score, 77);
+        assert!(data.leaderboard.entries().is_empty());
+        assert_eq!(data.lifetime, LifetimeStats::default());
+    }
+
+    #[test]
+    fn test_newer_version_is_error() {
+        let text = format!("version = {}\nhigh_score = 10\n", SAVE_VERSION + 1);
+        assert!(SaveData::from_toml(&text).is_err());
+    }
+
+    #[test]
+    fn test_record_run_keeps_best() {
+        let mut data = SaveData {
+            high_score: 500,
+            ..SaveData::default()
+        };
+        let mut game = Game::with_seed(0);
+        game.score = 300;
+        data.record_run(&game);
+        assert_eq!(data.high_score, 500);
+
+        game.score = 900;
+        data.record_run(&game);
+        assert_eq!(data.high_score, 900);
+    }
+
+    #[test]
+    fn test_lifetime_stats_carry_over() {
+        let mut game = Game::with_seed(0);
+        game.lifetime.runs = 3;
+        game.lifetime.coins_collected = 40;
+        let mut data = SaveData::default();
+        data.record_run(&game);
+
+        let loaded = SaveData::from_toml(&data.to_toml()).unwrap();
+        let mut next_session = Game::with_seed(1);
+        loaded.apply_to(&mut next_session);
+        assert_eq!(next_session.lifetime, game.lifetime);
+    }
+}
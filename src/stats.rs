@@ -0,0 +1,103 @@
+//! Per-run and lifetime statistics
+//!
+//! `Game` counts what happens during a run in `RunStats`, shown on the game
+//! over screen, and folds each finished run into `LifetimeStats`, which
+//! `SaveData` keeps across sessions.
+
+use crate::DeathCause;
+use serde::{Deserialize, Serialize};
+
+/// Counters for the current run, reset when a new run starts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunStats {
+    /// Frames spent in `GameState::Playing`
+    pub frames: u32,
+    pub coins_collected: u32,
+    /// Coins that sank or drifted away without being collected
+    pub coins_missed: u32,
+    pub fish_eaten: u32,
+    /// Missiles that flew off the screen without hitting the otter
+    pub missiles_dodged: u32,
+    /// Missiles blown up by ramming them while invincible
+    pub missiles_destroyed: u32,
+    /// Most coins collected in a single combo
+    pub best_streak: u32,
+    /// Missiles that grazed past the otter
+    pub near_misses: u32,
+    /// Frames spent walking along the seabed
+    pub walking_frames: u32,
+    /// What ended the run (None while it's still going)
+    pub cause_of_death: Option<DeathCause>,
+}
+
+/// Totals over every finished run
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub runs: u64,
+    pub frames: u64,
+    pub coins_collected: u64,
+    pub coins_missed: u64,
+    pub fish_eaten: u64,
+    pub missiles_dodged: u64,
+    pub missiles_destroyed: u64,
+    pub near_misses: u64,
+    pub walking_frames: u64,
+    /// Longest coin combo in any run
+    pub best_streak: u32,
+    // Runs ended by each hazard
+    pub missile_deaths: u64,
+    pub rock_deaths: u64,
+    pub crab_deaths: u64,
+}
+
+impl LifetimeStats {
+    /// Add a finished run
+    pub fn add_run(&mut self, run: &RunStats) {
+        self.runs += 1;
+        self.frames += u64::from(run.frames);
+        self.coins_collected += u64::from(run.coins_collected);
+        self.coins_missed += u64::from(run.coins_missed);
+        self.fish_eaten += u64::from(run.fish_eaten);
+        self.missiles_dodged += u64::from(run.missiles_dodged);
+        self.missiles_destroyed += u64::from(run.missiles_destroyed);
+        self.near_misses += u64::from(run.near_misses);
+        self.walking_frames += u64::from(run.walking_frames);
+        self.best_streak = self.best_streak.max(run.best_streak);
+        match run.cause_of_death {
+            Some(DeathCause::Missile) => self.missile_deaths += 1,
+            Some(DeathCause::Rock) => self.rock_deaths += 1,
+            Some(DeathCause::Crab) => self.crab_deaths += 1,
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifetime_adds_runs() {
+        let run = RunStats {
+            frames: 300,
+            coins_collected: 4,
+            best_streak: 3,
+            cause_of_death: Some(DeathCause::Rock),
+            ..RunStats::default()
+        };
+        let mut lifetime = LifetimeStats::default();
+        lifetime.add_run(&run);
+        lifetime.add_run(&RunStats {
+            best_streak: 2,
+            cause_of_death: Some(DeathCause::Missile),
+            ..run
+        });
+
+        assert_eq!(lifetime.runs, 2);
+        assert_eq!(lifetime.frames, 600);
+        assert_eq!(lifetime.coins_collected, 8);
+        assert_eq!(lifetime.best_streak, 3);
+        assert_eq!((lifetime.missile_deaths, lifetime.rock_deaths), (1, 1));
+    }
+}
@@ -8,7 +8,7 @@ fn setup_collision_test() -> Game {
     let mut game = Game::new();
     game.start();
     // Position otter in middle of screen, at the boundary so it won't move
-    game.otter.y = OTTER_MIN_Y; // Will stay here when swimming up
+    game.otter.motion.y = OTTER_MIN_Y; // Will stay here when swimming up
     game.otter.velocity_y = -OTTER_VELOCITY; // Swimming up
     game
 }
@@ -42,7 +42,7 @@ fn test_otter_stays_in_bounds_during_gameplay() {
     for _ in 0..200 {
         game.otter.swim_up();
         game.otter.update();
-        assert!(game.otter.y >= OTTER_MIN_Y, "Otter went above min Y");
+        assert!(game.otter.motion.y >= OTTER_MIN_Y, "Otter went above min Y");
     }
 
     // Simulate releasing space (swim down)
@@ -50,7 +50,7 @@ fn test_otter_stays_in_bounds_during_gameplay() {
         game.otter.swim_down();
         game.otter.update();
         assert!(
-            game.otter.y + OTTER_HEIGHT <= SCREEN_HEIGHT,
+            game.otter.motion.y + OTTER_HEIGHT <= SCREEN_HEIGHT,
             "Otter went below screen"
         );
     }
@@ -58,17 +58,76 @@ fn test_otter_stays_in_bounds_during_gameplay() {
 
 #[test]
 fn test_missile_spawning_and_cleanup() {
-    let mut game = Game::new();
+    let mut game = Game::with_seed(7);
     game.start();
 
     // Run many frames to spawn missiles
+    let mut max_missiles = 0;
     for _ in 0..500 {
         game.update();
+        max_missiles = max_missiles.max(game.missiles().count());
+        if game.state != GameState::Playing {
+            break;
+        }
+    }
+
+    // Missiles should have spawned, and off-screen ones been cleaned up
+    assert!(max_missiles > 0, "No missiles spawned");
+    assert!(game.missiles().all(|m| m.motion.x >= -50 || m.state == MissileState::Exploding));
+}
+
+/// Run a game for a fixed number of frames, alternating swim up/down
+fn run_scripted(seed: u64, frames: usize) -> Game {
+    let mut game = Game::with_seed(seed);
+    game.handle_space_pressed();
+    for frame in 0..frames {
+        if frame % 6 == 0 {
+            game.handle_space_pressed();
+        } else if frame % 6 == 3 {
+            game.handle_space_released();
+        }
+        game.update();
+    }
+    game
+}
+
+#[test]
+fn test_same_seed_same_run() {
+    let a = run_scripted(1234, 300);
+    let b = run_scripted(1234, 300);
+
+    assert_eq!(a.state, b.state);
+    assert_eq!(a.score, b.score);
+    assert_eq!(a.otter, b.otter);
+    assert_eq!(a.entities, b.entities);
+}
+
+#[test]
+fn test_different_seeds_differ() {
+    let a = run_scripted(1, 100);
+    let b = run_scripted(2, 100);
+
+    assert!(
+        a.entities != b.entities,
+        "Different seeds should spawn different entities"
+    );
+}
+
+#[test]
+fn test_restart_replays_same_seed() {
+    let mut game = Game::with_seed(99);
+    game.start();
+    for _ in 0..60 {
+        game.update();
     }
+    let first_coins: Vec<Coin> = game.coins().cloned().collect();
 
-    // Missiles should have spawned
-    // Some might still be active, some cleaned up
-    // Just verify no panics occurred
+    game.reset();
+    for _ in 0..60 {
+        game.update();
+    }
+
+    assert!(game.coins().eq(&first_coins), "A reset should replay the same spawns");
 }
 
 #[test]
@@ -76,18 +135,18 @@ fn test_coin_collection_increases_score() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect and place coin overlapping it
-    let (ox, oy, _ow, _oh) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Place coin directly overlapping otter's collision rect
     let mut coin = Coin::new(ox);
-    coin.y = oy;
-    game.coins.push(coin);
+    coin.motion.y = oy;
+    game.entities.push(coin.into());
 
     let initial_score = game.score;
 
     // Verify collision would occur
     let otter_rect = game.otter.get_collision_rect();
-    let coin_rect = game.coins[0].get_collision_rect();
+    let coin_rect = game.coins().next().unwrap().get_collision_rect();
     assert!(
         rects_collide(otter_rect, coin_rect),
         "Coin should overlap otter. Otter: {:?}, Coin: {:?}",
@@ -110,18 +169,18 @@ fn test_fish_grants_invincibility() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Place fish overlapping otter
     let mut fish = Fish::new(oy);
-    fish.x = ox;
-    game.fish.push(fish);
+    fish.motion.x = ox;
+    game.entities.push(fish.into());
 
     assert!(!game.otter.is_invincible);
 
     // Verify collision
     assert!(
-        rects_collide(game.otter.get_collision_rect(), game.fish[0].get_collision_rect()),
+        rects_collide(game.otter.get_collision_rect(), game.fish().next().unwrap().get_collision_rect()),
         "Fish should overlap otter"
     );
 
@@ -131,6 +190,7 @@ fn test_fish_grants_invincibility() {
         game.otter.is_invincible,
         "Otter should be invincible after eating fish"
     );
+    assert_eq!(game.stats.fish_eaten, 1);
 }
 
 #[test]
@@ -138,19 +198,19 @@ fn test_missile_collision_without_invincibility_ends_game() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Ensure not invincible
     assert!(!game.otter.is_invincible);
 
     // Place missile overlapping otter
     let mut missile = Missile::new(oy);
-    missile.x = ox;
-    game.missiles.push(missile);
+    missile.motion.x = ox;
+    game.entities.push(missile.into());
 
     // Verify collision
     assert!(
-        rects_collide(game.otter.get_collision_rect(), game.missiles[0].get_collision_rect()),
+        rects_collide(game.otter.get_collision_rect(), game.missiles().next().unwrap().get_collision_rect()),
         "Missile should overlap otter"
     );
 
@@ -168,19 +228,19 @@ fn test_missile_collision_with_invincibility_destroys_missile() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Make otter invincible
     game.otter.activate_invincibility();
 
     // Place missile overlapping otter
     let mut missile = Missile::new(oy);
-    missile.x = ox;
-    game.missiles.push(missile);
+    missile.motion.x = ox;
+    game.entities.push(missile.into());
 
     // Verify collision
     assert!(
-        rects_collide(game.otter.get_collision_rect(), game.missiles[0].get_collision_rect()),
+        rects_collide(game.otter.get_collision_rect(), game.missiles().next().unwrap().get_collision_rect()),
         "Missile should overlap otter"
     );
 
@@ -190,7 +250,56 @@ fn test_missile_collision_with_invincibility_destroys_missile() {
     assert_eq!(game.state, GameState::Playing);
 
     // Missile should be exploding
-    assert_eq!(game.missiles[0].state, MissileState::Exploding);
+    assert_eq!(game.missiles().next().unwrap().state, MissileState::Exploding);
+    assert_eq!(game.stats.missiles_destroyed, 1);
+}
+
+/// A game whose otter has just landed on the seabed
+fn setup_walking_test() -> Game {
+    let mut game = Game::with_config(
+        config::GameConfig {
+            rock_spawn_chance: 0,
+            crab_spawn_chance: 0,
+            ..Default::default()
+        },
+        7,
+    );
+    game.start();
+    game.otter.motion.y = OTTER_WALK_Y;
+    game.update();
+    assert_eq!(game.otter.state, OtterState::Walking);
+    game
+}
+
+#[test]
+fn test_seabed_hazards_end_a_walking_run() {
+    // Place each hazard so it reaches the otter on the next update
+    let mut game = setup_walking_test();
+    let mut crab = Crab::new();
+    crab.motion.x = game.otter.motion.x + crab.velocity_x;
+    game.entities.push(crab.into());
+    game.update();
+    assert_eq!(game.state, GameState::GameOver, "A crab should nip a walking otter");
+
+    let mut game = setup_walking_test();
+    let mut rock = Rock::new();
+    rock.motion.x = game.otter.motion.x + rock.velocity_x;
+    game.entities.push(rock.into());
+    game.update();
+    assert_eq!(game.state, GameState::GameOver, "Walking into a rock should end the run");
+}
+
+#[test]
+fn test_swimming_otter_passes_over_seabed_hazards() {
+    let mut game = setup_collision_test();
+    let mut rock = Rock::new();
+    rock.motion.x = game.otter.motion.x + rock.velocity_x;
+    let mut crab = Crab::new();
+    crab.motion.x = game.otter.motion.x + crab.velocity_x;
+    game.entities.push(rock.into());
+    game.entities.push(crab.into());
+    game.update();
+    assert_eq!(game.state, GameState::Playing);
 }
 
 #[test]
@@ -198,15 +307,15 @@ fn test_high_score_updates_on_game_over() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Give some score
     game.score = 500;
 
     // Place missile to trigger game over
     let mut missile = Missile::new(oy);
-    missile.x = ox;
-    game.missiles.push(missile);
+    missile.motion.x = ox;
+    game.entities.push(missile.into());
 
     game.update();
 
@@ -231,16 +340,16 @@ fn test_sound_effects_generated() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Place coin overlapping otter
     let mut coin = Coin::new(ox);
-    coin.y = oy;
-    game.coins.push(coin);
+    coin.motion.y = oy;
+    game.entities.push(coin.into());
 
     game.update();
 
-    let sounds = game.take_pending_sounds();
+    let sounds: Vec<_> = game.take_events().iter().filter_map(GameEvent::sound).collect();
     assert!(
         sounds.contains(&SoundEffect::Coin),
         "Coin sound should be triggered. Got sounds: {:?}",
@@ -269,14 +378,14 @@ fn test_invincibility_score_based_expiry() {
     game.score = 500;
 
     // Get otter collision rect and place fish overlapping
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
     let mut fish = Fish::new(oy);
-    fish.x = ox;
-    game.fish.push(fish);
+    fish.motion.x = ox;
+    game.entities.push(fish.into());
 
     // Verify collision would occur
     assert!(
-        rects_collide(game.otter.get_collision_rect(), game.fish[0].get_collision_rect()),
+        rects_collide(game.otter.get_collision_rect(), game.fish().next().unwrap().get_collision_rect()),
         "Fish should overlap otter"
     );
 
@@ -285,7 +394,7 @@ fn test_invincibility_score_based_expiry() {
     assert!(game.otter.is_invincible, "Should be invincible after eating fish");
 
     // invincibility_check_score should be: score after update (507) + INVINCIBILITY_SCORE_DURATION (2000) = 2507
-    let check_score = game.invincibility_check_score.expect("Should have check score set");
+    let check_score = game.invincibility_check_score().expect("Should have check score set");
 
     // Set score so that after update (which adds SCORE_PER_FRAME), we're still below threshold
     game.score = check_score - SCORE_PER_FRAME - 1;
@@ -303,35 +412,599 @@ fn test_multiple_coins_collection() {
     let mut game = setup_collision_test();
 
     // Get otter collision rect
-    let (ox, oy, _, _) = game.otter.get_collision_rect();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
 
     // Add multiple coins at otter position
     for _ in 0..5 {
         let mut coin = Coin::new(ox);
-        coin.y = oy;
-        game.coins.push(coin);
+        coin.motion.y = oy;
+        game.entities.push(coin.into());
     }
 
     game.update();
 
-    // Score is: SCORE_PER_FRAME (7) + 5 * COIN_SCORE (100 each)
-    assert_eq!(game.score, SCORE_PER_FRAME + 5 * COIN_SCORE, "Should collect all 5 coins plus per-frame score");
+    // Score is: SCORE_PER_FRAME (7) + COIN_SCORE (100) at combo multipliers x1 to x5
+    assert_eq!(
+        game.score,
+        SCORE_PER_FRAME + (1 + 2 + 3 + 4 + 5) * COIN_SCORE,
+        "Should collect all 5 coins plus per-frame score"
+    );
+    assert_eq!(game.stats.coins_collected, 5);
 }
 
 #[test]
 fn test_collision_detection_function() {
     // Exact overlap
-    assert!(rects_collide((0, 0, 10, 10), (0, 0, 10, 10)));
+    assert!(rects_collide(Rect::new(0, 0, 10, 10), Rect::new(0, 0, 10, 10)));
 
     // Partial overlap
-    assert!(rects_collide((0, 0, 10, 10), (5, 5, 10, 10)));
+    assert!(rects_collide(Rect::new(0, 0, 10, 10), Rect::new(5, 5, 10, 10)));
 
     // No overlap - to the right
-    assert!(!rects_collide((0, 0, 10, 10), (20, 0, 10, 10)));
+    assert!(!rects_collide(Rect::new(0, 0, 10, 10), Rect::new(20, 0, 10, 10)));
 
     // No overlap - below
-    assert!(!rects_collide((0, 0, 10, 10), (0, 20, 10, 10)));
+    assert!(!rects_collide(Rect::new(0, 0, 10, 10), Rect::new(0, 20, 10, 10)));
 
     // Edge touching (should not collide)
-    assert!(!rects_collide((0, 0, 10, 10), (10, 0, 10, 10)));
+    assert!(!rects_collide(Rect::new(0, 0, 10, 10), Rect::new(10, 0, 10, 10)));
+}
+
+#[test]
+fn test_replay_reproduces_run() {
+    use otter_swag::input::InputAction;
+    use otter_swag::replay::{Replay, ReplayInput, ReplayRecorder};
+
+    // Record a run with an irregular input pattern until the otter dies
+    let mut game = Game::with_seed(2024);
+    let mut recorder = ReplayRecorder::new(game.seed(), &game.config);
+    recorder.apply(ReplayInput::Pressed(InputAction::Swim), &mut game);
+    let mut frame = 0u32;
+    while game.state == GameState::Playing && frame < 10_000 {
+        match frame % 11 {
+            0 | 5 => recorder.apply(ReplayInput::Pressed(InputAction::Swim), &mut game),
+            3 | 9 => recorder.apply(ReplayInput::Released(InputAction::Swim), &mut game),
+            _ => {}
+        }
+        game.update();
+        recorder.end_frame();
+        frame += 1;
+    }
+    assert_eq!(game.state, GameState::GameOver, "Scripted run should end");
+
+    // Round-trip through the file format and play it back
+    let replay = Replay::from_bytes(&recorder.finish().to_bytes()).unwrap();
+    let replayed = replay.run().unwrap();
+
+    assert_eq!(replayed.state, GameState::GameOver);
+    assert_eq!(replayed.score, game.score);
+    assert_eq!(replayed.otter, game.otter);
+    assert_eq!(replayed.entities, game.entities);
+    assert_eq!(replay.frame_count, frame);
+}
+
+#[test]
+fn test_pause_does_not_change_run() {
+    use otter_swag::input::InputAction;
+    use otter_swag::replay::{ReplayInput, ReplayRecorder};
+
+    // The same inputs, with and without a pause in the middle, end the same way
+    let run = |pause_at: Option<u32>| {
+        let mut game = Game::with_seed(77);
+        let mut recorder = ReplayRecorder::new(game.seed(), &game.config);
+        recorder.apply(ReplayInput::Pressed(InputAction::Swim), &mut game);
+        let mut frame = 0u32;
+        let mut playing_frames = 0u32;
+        while game.state != GameState::GameOver && frame < 10_000 {
+            if Some(frame) == pause_at {
+                recorder.apply(ReplayInput::Pressed(InputAction::Pause), &mut game);
+            }
+            if Some(frame) == pause_at.map(|f| f + 25) {
+                recorder.apply(ReplayInput::Pressed(InputAction::Pause), &mut game);
+            }
+            if game.state == GameState::Playing && game.resume_countdown == 0 {
+                match playing_frames % 9 {
+                    0 => recorder.apply(ReplayInput::Pressed(InputAction::Swim), &mut game),
+                    4 => recorder.apply(ReplayInput::Released(InputAction::Swim), &mut game),
+                    _ => {}
+                }
+                playing_frames += 1;
+            }
+            game.update();
+            recorder.end_frame();
+            frame += 1;
+        }
+        (game, recorder.finish())
+    };
+
+    let (plain, _) = run(None);
+    let (paused, replay) = run(Some(40));
+    assert_eq!(paused.state, GameState::GameOver);
+    assert_eq!(paused.score, plain.score);
+    assert_eq!(paused.stats.frames, plain.stats.frames);
+
+    let replayed = replay.run().unwrap();
+    assert_eq!(replayed.score, paused.score);
+    assert_eq!(replayed.otter, paused.otter);
+}
+
+#[test]
+fn test_swim_alone_enters_initials_and_restarts() {
+    use otter_swag::input::InputAction;
+    use otter_swag::leaderboard::INITIALS_HOLD_FRAMES;
+
+    // A touchscreen or mouse only ever sends Swim
+    let mut game = setup_collision_test();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox;
+    game.entities.push(missile.into());
+    game.update();
+    assert!(game.is_entering_initials());
+
+    let tap = |game: &mut Game| {
+        game.handle_action(InputAction::Swim, true);
+        game.update();
+        game.handle_action(InputAction::Swim, false);
+    };
+    let hold = |game: &mut Game| {
+        game.handle_action(InputAction::Swim, true);
+        for _ in 0..INITIALS_HOLD_FRAMES {
+            game.update();
+        }
+        game.handle_action(InputAction::Swim, false);
+    };
+
+    tap(&mut game);
+    tap(&mut game);
+    hold(&mut game);
+    hold(&mut game);
+    tap(&mut game);
+    assert_eq!(game.state, GameState::GameOver);
+    hold(&mut game);
+
+    assert!(!game.is_entering_initials());
+    assert_eq!(game.leaderboard.entries()[0].initials, "CAB");
+
+    game.handle_action(InputAction::Swim, true);
+    assert_eq!(game.state, GameState::Playing);
+}
+
+#[test]
+fn test_leaderboard_initials_after_game_over() {
+    use otter_swag::leaderboard::InitialsInput;
+
+    let mut game = setup_collision_test();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox;
+    game.entities.push(missile.into());
+    game.update();
+
+    assert_eq!(game.state, GameState::GameOver);
+    assert!(game.is_entering_initials(), "Empty leaderboard should accept any run");
+
+    // Space cycles the letter instead of restarting
+    game.handle_space_pressed();
+    game.handle_space_released();
+    assert_eq!(game.state, GameState::GameOver);
+
+    assert_eq!(game.handle_initials_input(InitialsInput::Confirm), None);
+    assert_eq!(game.handle_initials_input(InitialsInput::Letter('t')), None);
+    assert_eq!(game.handle_initials_input(InitialsInput::Letter('r')), Some(0));
+
+    let entry = &game.leaderboard.entries()[0];
+    assert_eq!(entry.initials, "BTR");
+    assert_eq!(entry.score, game.score);
+    assert_eq!(entry.seed, game.seed());
+    assert!(!game.is_entering_initials());
+
+    // Now space starts a new run
+    game.handle_space_pressed();
+    assert_eq!(game.state, GameState::Playing);
+}
+
+#[test]
+fn test_config_overrides_tuning() {
+    use otter_swag::config::GameConfig;
+
+    let config = GameConfig::from_toml("coin_score = 250\nscore_per_frame = 1\nmissile_velocity_x = 5\n").unwrap();
+    let mut game = Game::with_config(config, 11);
+    game.start();
+    game.otter.motion.y = OTTER_MIN_Y;
+    game.otter.velocity_y = -OTTER_VELOCITY;
+
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut coin = Coin::new(ox);
+    coin.motion.y = oy;
+    game.entities.push(coin.into());
+    game.update();
+    assert_eq!(game.score, 1 + 250);
+
+    // Spawned missiles use the configured speed
+    while game.missiles().next().is_none() {
+        game.update();
+    }
+    assert_eq!(game.missiles().next().unwrap().velocity_x, 5);
+}
+
+#[test]
+fn test_precise_collision_needs_touching_sprites() {
+    use otter_swag::config::GameConfig;
+    use otter_swag::mask::{CollisionMasks, Image, Mask};
+    use otter_swag::render::TextureId;
+    use std::sync::Arc;
+
+    // Real sheets, except missiles that are entirely see-through
+    let mut masks = CollisionMasks::load().unwrap();
+    for &clip in MISSILE_CLIPS_SHOOT {
+        let clear = Image::new(clip.w, clip.h, vec![[0, 0, 0, 0]; (clip.w * clip.h) as usize]);
+        let mask = Mask::from_image(&clear, ClipRect::new(0, 0, clip.w, clip.h));
+        masks.insert(TextureId::Missile, clip, mask);
+    }
+    let masks = Arc::new(masks);
+
+    let run = |precise_collision: bool| {
+        let config = GameConfig {
+            precise_collision,
+            ..GameConfig::default()
+        };
+        let mut game = Game::with_config(config, 5);
+        game.set_collision_masks(Some(masks.clone()));
+        game.start();
+        game.otter.motion.y = OTTER_MIN_Y;
+        game.otter.velocity_y = -OTTER_VELOCITY;
+        let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+        let mut missile = Missile::new(oy);
+        missile.motion.x = ox;
+        game.entities.push(missile.into());
+        game.update();
+        game.state
+    };
+
+    assert_eq!(run(false), GameState::GameOver, "Hitboxes alone collide");
+    assert_eq!(run(true), GameState::Playing, "Invisible missile can't hit");
+}
+
+#[test]
+fn test_power_up_pickup_starts_effect() {
+    let mut game = setup_collision_test();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut pickup = PowerUpPickup::new(PowerUp::SlowMotion, oy, POWER_UP_VELOCITY_X);
+    pickup.motion.x = ox + POWER_UP_VELOCITY_X;
+    game.entities.push(pickup.into());
+    game.update();
+
+    assert_eq!(
+        game.power_ups.expiry(PowerUp::SlowMotion),
+        Some(Expiry::AfterFrames(SLOW_MOTION_FRAMES))
+    );
+    assert!(game
+        .take_events()
+        .contains(&GameEvent::PowerUpStarted(PowerUp::SlowMotion)));
+
+    // Runs out after its frames
+    for _ in 0..SLOW_MOTION_FRAMES {
+        game.power_ups.expiry(PowerUp::SlowMotion).expect("Still slowed");
+        game.update();
+    }
+    assert!(!game.power_ups.is_active(PowerUp::SlowMotion));
+}
+
+#[test]
+fn test_magnet_pulls_nearby_coins() {
+    let coin_distance = |magnet: bool| {
+        let mut game = Game::with_seed(4);
+        game.start();
+        game.otter.motion.y = OTTER_MIN_Y + 60;
+        game.otter.velocity_y = 0;
+        if magnet {
+            game.start_power_up(PowerUp::Magnet);
+        }
+        let (ox, oy) = game.otter.get_collision_rect().center();
+        // Coin centered 80 pixels right of the otter, holding still
+        let mut coin = Coin::with_velocity(ox + 80 - 8, 0, 0);
+        coin.motion.y = oy - 8;
+        game.entities.push(coin.into());
+        game.update();
+        let (cx, _) = game.coins().next().unwrap().get_collision_rect().center();
+        cx - game.otter.get_collision_rect().center().0
+    };
+
+    assert_eq!(coin_distance(false), 80);
+    assert_eq!(coin_distance(true), 80 - otter_swag::powerup::MAGNET_PULL);
+}
+
+#[test]
+fn test_shield_absorbs_one_hit() {
+    let mut game = setup_collision_test();
+    game.start_power_up(PowerUp::Shield);
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox + MISSILE_VELOCITY_X;
+    game.entities.push(missile.into());
+    game.update();
+
+    assert_eq!(game.state, GameState::Playing, "Shield takes the hit");
+    assert!(!game.power_ups.is_active(PowerUp::Shield));
+    assert_eq!(game.missiles().next().unwrap().state, MissileState::Exploding);
+
+    // The next hit ends the run
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox + MISSILE_VELOCITY_X;
+    game.entities.push(missile.into());
+    game.update();
+    assert_eq!(game.state, GameState::GameOver);
+}
+
+#[test]
+fn test_slow_motion_halves_hazard_speed() {
+    let mut game = setup_collision_test();
+    game.start_power_up(PowerUp::SlowMotion);
+    game.entities.push(Missile::new(200).into());
+    game.entities.push(Rock::new().into());
+    game.update();
+
+    assert_eq!(
+        game.missiles().next().unwrap().motion.x,
+        SCREEN_WIDTH - MISSILE_VELOCITY_X / 2
+    );
+    let rock = game.entities.iter().find(|e| matches!(e, AnyEntity::Rock(_)));
+    assert_eq!(rock.unwrap().position().0, SCREEN_WIDTH - SEABED_VELOCITY_X / 2);
+}
+
+/// Like `setup_collision_test`, but with no coins or pickups spawning on their own
+fn setup_combo_test() -> Game {
+    use otter_swag::config::GameConfig;
+
+    let config = GameConfig {
+        coin_spawn_chance: 0,
+        power_up_spawn_chance: 0,
+        ..GameConfig::default()
+    };
+    let mut game = Game::with_config(config, 0);
+    game.start();
+    game.otter.motion.y = OTTER_MIN_Y;
+    game.otter.velocity_y = -OTTER_VELOCITY;
+    game
+}
+
+/// Put a coin right on the otter, collected on the next update
+fn push_coin_on_otter(game: &mut Game) {
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut coin = Coin::with_velocity(ox, 0, 0);
+    coin.motion.y = oy;
+    game.entities.push(coin.into());
+}
+
+#[test]
+fn test_coin_combo_builds_and_times_out() {
+    let mut game = setup_combo_test();
+    push_coin_on_otter(&mut game);
+    game.update();
+    assert_eq!(game.score, SCORE_PER_FRAME + COIN_SCORE);
+
+    // The next coin inside the window is worth double
+    push_coin_on_otter(&mut game);
+    game.update();
+    assert_eq!(game.combo_multiplier(), 2);
+    assert_eq!(game.score, 2 * SCORE_PER_FRAME + 3 * COIN_SCORE);
+
+    // Waiting out the window drops back to single coins
+    for _ in 0..COMBO_WINDOW_FRAMES {
+        game.update();
+    }
+    assert_eq!(game.combo_multiplier(), 1);
+    let score = game.score;
+    push_coin_on_otter(&mut game);
+    game.update();
+    assert_eq!(game.score, score + SCORE_PER_FRAME + COIN_SCORE);
+    assert_eq!(game.stats.best_streak, 2);
+}
+
+#[test]
+fn test_missed_coin_breaks_combo() {
+    let mut game = setup_combo_test();
+    push_coin_on_otter(&mut game);
+    game.update();
+    push_coin_on_otter(&mut game);
+    game.update();
+    assert_eq!(game.coin_streak, 2);
+
+    // A coin about to sink past the bottom, far from the otter
+    let mut coin = Coin::new(300);
+    coin.motion.y = COIN_BOTTOM_THRESHOLD;
+    game.entities.push(coin.into());
+    game.update();
+    assert_eq!(game.coin_streak, 0);
+    assert_eq!(game.stats.best_streak, 2);
+}
+
+/// Fly a missile past the otter `gap` pixels below its hitbox; returns near misses scored
+fn near_misses_with_gap(game: &mut Game, gap: i32) -> u32 {
+    let otter_rect = game.otter.get_collision_rect();
+    game.entities.push(Missile::new(otter_rect.bottom() + gap).into());
+    while game.missiles().next().is_some_and(|m| m.motion.x > -50) {
+        game.update();
+        assert_eq!(game.state, GameState::Playing, "Missile shouldn't hit");
+    }
+    game.stats.near_misses
+}
+
+#[test]
+fn test_near_miss_scored_once() {
+    let mut game = setup_collision_test();
+    assert_eq!(near_misses_with_gap(&mut game, NEAR_MISS_MARGIN - 2), 1);
+    assert!(game
+        .take_events()
+        .iter()
+        .any(|e| matches!(e, GameEvent::NearMiss { .. })));
+
+    // Too far away to count
+    let mut game = setup_collision_test();
+    assert_eq!(near_misses_with_gap(&mut game, NEAR_MISS_MARGIN + 2), 0);
+}
+
+#[test]
+fn test_no_near_miss_while_invincible() {
+    let mut game = setup_collision_test();
+    game.start_power_up(PowerUp::Invincibility);
+    assert_eq!(near_misses_with_gap(&mut game, 2), 0);
+}
+
+#[test]
+fn test_near_miss_bonus_score() {
+    use otter_swag::config::GameConfig;
+
+    let config = GameConfig {
+        near_miss_score: 1000,
+        score_per_frame: 0,
+        coin_spawn_chance: 0,
+        ..GameConfig::default()
+    };
+    let mut game = Game::with_config(config, 2);
+    game.start();
+    game.otter.motion.y = OTTER_MIN_Y;
+    game.otter.velocity_y = -OTTER_VELOCITY;
+    assert_eq!(near_misses_with_gap(&mut game, 1), 1);
+    assert_eq!(game.score, 1000);
+}
+
+#[test]
+fn test_coin_and_game_over_events() {
+    let mut game = setup_collision_test();
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut coin = Coin::with_velocity(ox, 0, 0);
+    coin.motion.y = oy;
+    let (cx, cy) = coin.get_collision_rect().center();
+    game.entities.push(coin.into());
+    game.update();
+    assert_eq!(
+        game.take_events(),
+        vec![GameEvent::CoinCollected {
+            x: cx,
+            y: cy,
+            value: COIN_SCORE
+        }]
+    );
+
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox + MISSILE_VELOCITY_X;
+    game.entities.push(missile.into());
+    game.update();
+    assert_eq!(
+        game.take_events(),
+        vec![GameEvent::GameOver {
+            score: game.score,
+            cause: DeathCause::Missile
+        }]
+    );
+}
+
+#[test]
+fn test_power_up_and_landing_events() {
+    use otter_swag::config::GameConfig;
+
+    // Nothing on the seabed to end the run
+    let config = GameConfig {
+        rock_spawn_chance: 0,
+        crab_spawn_chance: 0,
+        ..GameConfig::default()
+    };
+    let mut game = Game::with_config(config, 8);
+    game.start();
+    game.start_power_up(PowerUp::SlowMotion);
+    assert_eq!(
+        game.take_events(),
+        vec![GameEvent::PowerUpStarted(PowerUp::SlowMotion)]
+    );
+
+    // Sink to the seabed; slow motion runs out along the way
+    let mut events = Vec::new();
+    while game.otter.state != OtterState::Walking {
+        game.update();
+        events.extend(game.take_events());
+    }
+    assert!(events.contains(&GameEvent::OtterLanded));
+    for _ in 0..SLOW_MOTION_FRAMES {
+        game.update();
+        events.extend(game.take_events());
+    }
+    assert!(events.contains(&GameEvent::PowerUpEnded(PowerUp::SlowMotion)));
+}
+
+#[test]
+fn test_high_score_beaten_once_per_run() {
+    let mut game = setup_collision_test();
+    game.high_score = 100;
+    let beaten = |events: Vec<GameEvent>| {
+        events
+            .iter()
+            .filter(|e| matches!(e, GameEvent::HighScoreBeaten { previous: 100 }))
+            .count()
+    };
+
+    game.score = 100 - SCORE_PER_FRAME;
+    game.update();
+    assert_eq!(beaten(game.take_events()), 0, "Only matched the high score");
+    game.update();
+    assert_eq!(beaten(game.take_events()), 1);
+    game.update();
+    assert_eq!(beaten(game.take_events()), 0);
+}
+
+#[test]
+fn test_run_stats_and_lifetime_totals() {
+    let mut game = setup_combo_test();
+
+    // A coin sinking out of reach, and a missile flying well below the otter
+    let mut coin = Coin::new(300);
+    coin.motion.y = COIN_BOTTOM_THRESHOLD;
+    game.entities.push(coin.into());
+    game.entities.push(Missile::new(200).into());
+    while game.missiles().next().is_some() {
+        game.update();
+    }
+    assert_eq!(game.stats.coins_missed, 1);
+    assert_eq!(game.stats.missiles_dodged, 1);
+    assert_eq!(game.stats.walking_frames, 0);
+
+    // This one hits
+    let Rect { x: ox, y: oy, .. } = game.otter.get_collision_rect();
+    let mut missile = Missile::new(oy);
+    missile.motion.x = ox + MISSILE_VELOCITY_X;
+    game.entities.push(missile.into());
+    game.update();
+    assert_eq!(game.state, GameState::GameOver);
+    assert_eq!(game.stats.cause_of_death, Some(DeathCause::Missile));
+    assert_eq!(game.stats.missiles_dodged, 1);
+    assert_eq!(game.lifetime.runs, 1);
+    assert_eq!(game.lifetime.missile_deaths, 1);
+    assert_eq!(game.lifetime.frames, u64::from(game.stats.frames));
+
+    // Lifetime totals outlast the next run's reset
+    game.start();
+    assert_eq!(game.stats, RunStats::default());
+    assert_eq!(game.lifetime.runs, 1);
+}
+
+#[test]
+fn test_walking_time_counted() {
+    use otter_swag::config::GameConfig;
+
+    let config = GameConfig {
+        rock_spawn_chance: 0,
+        crab_spawn_chance: 0,
+        ..GameConfig::default()
+    };
+    let mut game = Game::with_config(config, 3);
+    game.start();
+    while game.otter.state != OtterState::Walking {
+        game.update();
+    }
+    assert_eq!(game.stats.walking_frames, 1);
+    for _ in 0..5 {
+        game.update();
+    }
+    assert_eq!(game.stats.walking_frames, 6);
 }